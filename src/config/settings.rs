@@ -0,0 +1,474 @@
+use crate::git::{default_ignore, DiscoveryOptions};
+use crate::state::Rules;
+use anyhow::{Context, Result};
+use chrono::{NaiveTime, Weekday};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// User configuration, read from `config.toml` in the XDG config directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// Author names or emails whose commits feed Kani.
+    /// When empty, each repository's `user.email` is used instead.
+    pub authors: Vec<String>,
+    /// How many days of git history to import on startup (0 disables backfill)
+    pub backfill_days: u32,
+    /// Where to look for repositories
+    pub discovery: DiscoveryConfig,
+    /// How to watch repositories for commits
+    pub watch: WatchConfig,
+    /// Reminders about unpushed work
+    pub push: PushConfig,
+    /// How commits turn into happiness
+    pub happiness: HappinessConfig,
+    /// Which days count as the weekend
+    pub schedule: ScheduleConfig,
+    /// Timings of the running app
+    pub behavior: BehaviorConfig,
+    /// Repositories passed with `--repo` (command line only)
+    #[serde(skip)]
+    pub cli_repos: Vec<PathBuf>,
+}
+
+/// Repository discovery settings (the `[discovery]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiscoveryConfig {
+    /// Directories scanned for repositories in addition to the current one
+    pub roots: Vec<PathBuf>,
+    /// How many directory levels below a root to search (the current folder gets one)
+    pub max_depth: usize,
+    /// Glob patterns for directories to skip while scanning
+    pub ignore: Vec<String>,
+    /// Also track submodules of discovered repositories
+    pub submodules: bool,
+}
+
+/// File watching settings (the `[watch]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WatchConfig {
+    /// `auto` uses filesystem events and falls back to polling per repo, `poll` always polls
+    pub mode: WatchSetting,
+    /// Seconds between HEAD checks for polled repositories
+    pub poll_interval_secs: u64,
+}
+
+/// Unpushed work settings (the `[push]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PushConfig {
+    /// Hours a commit can stay unpushed before Kani starts nagging (0 disables)
+    pub nag_after_hours: u64,
+}
+
+/// Happiness settings (the `[happiness]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct HappinessConfig {
+    /// Commits in one day that bring Kani to full happiness
+    pub full_at_commits: u32,
+}
+
+/// Calendar settings (the `[schedule]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ScheduleConfig {
+    /// Days that don't need a commit to keep the streak going
+    pub weekend_days: Vec<Weekday>,
+    /// Time a new day begins (`"HH:MM"`, before noon); earlier commits count for the day before
+    #[serde(with = "hour_minute")]
+    pub day_starts_at: NaiveTime,
+}
+
+/// App behavior settings (the `[behavior]` table)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BehaviorConfig {
+    /// Seconds between automatic saves of the state file
+    pub autosave_secs: u64,
+    /// Seconds Kani's reactions (to commits, pushes, games) stay on screen
+    pub message_secs: u64,
+}
+
+/// Where the effective value of a setting comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueSource {
+    /// Built-in default
+    Default,
+    /// Set in the config file
+    File,
+    /// Passed as a command line flag
+    CommandLine,
+}
+
+impl std::fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueSource::Default => write!(f, "default"),
+            ValueSource::File => write!(f, "config file"),
+            ValueSource::CommandLine => write!(f, "command line"),
+        }
+    }
+}
+
+/// One setting as shown by `kanitomo config`
+#[derive(Debug, Clone)]
+pub struct ConfigValue {
+    /// Dotted key, as written in the config file (e.g. `watch.mode`)
+    pub key: String,
+    /// Effective value in TOML syntax
+    pub value: String,
+    /// Where the value comes from
+    pub source: ValueSource,
+}
+
+/// Which watching strategy to use
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchSetting {
+    /// Filesystem events, polling repos where they don't arrive
+    Auto,
+    /// Always poll (for network filesystems)
+    Poll,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            authors: Vec::new(),
+            backfill_days: 30,
+            discovery: DiscoveryConfig::default(),
+            watch: WatchConfig::default(),
+            push: PushConfig::default(),
+            happiness: HappinessConfig::default(),
+            schedule: ScheduleConfig::default(),
+            behavior: BehaviorConfig::default(),
+            cli_repos: Vec::new(),
+        }
+    }
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        let options = DiscoveryOptions::default();
+        Self {
+            roots: options.roots,
+            max_depth: options.max_depth,
+            ignore: default_ignore(),
+            submodules: options.submodules,
+        }
+    }
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            mode: WatchSetting::Auto,
+            poll_interval_secs: 5,
+        }
+    }
+}
+
+impl Default for PushConfig {
+    fn default() -> Self {
+        Self {
+            nag_after_hours: 24,
+        }
+    }
+}
+
+impl Default for HappinessConfig {
+    fn default() -> Self {
+        Self {
+            full_at_commits: Rules::default().full_happiness_commits,
+        }
+    }
+}
+
+impl Default for ScheduleConfig {
+    fn default() -> Self {
+        Self {
+            weekend_days: Rules::default().weekend_days,
+            day_starts_at: Rules::default().day_start,
+        }
+    }
+}
+
+impl Default for BehaviorConfig {
+    fn default() -> Self {
+        Self {
+            autosave_secs: 60,
+            message_secs: 30,
+        }
+    }
+}
+
+impl Config {
+    /// Path to the config file (`~/.config/kanitomo/config.toml` on Linux)
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("kanitomo").join("config.toml"))
+    }
+
+    /// Read the config file (None if it doesn't exist)
+    fn read() -> Result<Option<(PathBuf, String)>> {
+        let Some(path) = Self::path() else {
+            return Ok(None);
+        };
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let contents = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
+        Ok(Some((path, contents)))
+    }
+
+    /// Load the config file, falling back to defaults if it doesn't exist
+    pub fn load() -> Result<Self> {
+        match Self::read()? {
+            Some((path, contents)) => Self::parse(&path, &contents),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Parse and validate the contents of the config file at `path`
+    fn parse(path: &Path, contents: &str) -> Result<Self> {
+        let config: Self = toml::from_str(contents)
+            .with_context(|| format!("Failed to parse config file {}", path.display()))?;
+
+        for pattern in &config.discovery.ignore {
+            glob::Pattern::new(pattern).with_context(|| {
+                format!(
+                    "Invalid pattern '{}' in discovery.ignore ({})",
+                    pattern,
+                    path.display()
+                )
+            })?;
+        }
+
+        let at_least_one = [
+            ("watch.poll_interval_secs", config.watch.poll_interval_secs),
+            (
+                "happiness.full_at_commits",
+                config.happiness.full_at_commits as u64,
+            ),
+            ("behavior.autosave_secs", config.behavior.autosave_secs),
+            ("behavior.message_secs", config.behavior.message_secs),
+        ];
+        for (key, value) in at_least_one {
+            if value == 0 {
+                anyhow::bail!("{} must be at least 1 ({})", key, path.display());
+            }
+        }
+
+        // A later start would file most of a day's commits under the day before
+        if config.schedule.day_starts_at >= NaiveTime::from_hms_opt(12, 0, 0).unwrap() {
+            anyhow::bail!(
+                "schedule.day_starts_at must be before 12:00 ({})",
+                path.display()
+            );
+        }
+
+        let weekend: HashSet<Weekday> = config.schedule.weekend_days.iter().copied().collect();
+        if weekend.len() == 7 {
+            anyhow::bail!(
+                "schedule.weekend_days must leave at least one day that needs a commit ({})",
+                path.display()
+            );
+        }
+
+        Ok(config)
+    }
+
+    /// Every setting with its effective value and where it comes from
+    pub fn values(&self) -> Result<Vec<ConfigValue>> {
+        let from_file: toml::Table = match Self::read()? {
+            Some((path, contents)) => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse config file {}", path.display()))?,
+            None => toml::Table::new(),
+        };
+        self.values_from(&from_file)
+    }
+
+    /// Every setting, labeled as set in the config file when `from_file` has it
+    fn values_from(&self, from_file: &toml::Table) -> Result<Vec<ConfigValue>> {
+        let effective = toml::Table::try_from(self).context("Failed to serialize config")?;
+
+        let mut values = Vec::new();
+        let mut push = |key: String, value: &toml::Value, in_file: bool| {
+            values.push(ConfigValue {
+                key,
+                value: value.to_string(),
+                source: if in_file {
+                    ValueSource::File
+                } else {
+                    ValueSource::Default
+                },
+            });
+        };
+        for (key, value) in &effective {
+            match value {
+                toml::Value::Table(table) => {
+                    let file_table = from_file.get(key).and_then(toml::Value::as_table);
+                    for (name, value) in table {
+                        let in_file = file_table.is_some_and(|t| t.contains_key(name));
+                        push(format!("{}.{}", key, name), value, in_file);
+                    }
+                }
+                _ => push(key.clone(), value, from_file.contains_key(key)),
+            }
+        }
+
+        if !self.cli_repos.is_empty() {
+            let repos: Vec<String> = self
+                .cli_repos
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect();
+            values.push(ConfigValue {
+                key: "--repo".to_string(),
+                value: toml::Value::from(repos).to_string(),
+                source: ValueSource::CommandLine,
+            });
+        }
+
+        Ok(values)
+    }
+
+    /// Happiness and streak rules for the state module
+    pub fn rules(&self) -> Rules {
+        Rules {
+            full_happiness_commits: self.happiness.full_at_commits,
+            weekend_days: self.schedule.weekend_days.clone(),
+            day_start: self.schedule.day_starts_at,
+        }
+    }
+
+    /// Build repository discovery options from the config and command line
+    pub fn discovery_options(&self) -> DiscoveryOptions {
+        DiscoveryOptions {
+            repos: self.cli_repos.clone(),
+            roots: self
+                .discovery
+                .roots
+                .iter()
+                .map(|root| expand_home(root))
+                .collect(),
+            max_depth: self.discovery.max_depth,
+            ignore: self.discovery.ignore.clone(),
+            submodules: self.discovery.submodules,
+        }
+    }
+}
+
+/// Times of day written as `"HH:MM"` in the config file
+mod hour_minute {
+    use chrono::NaiveTime;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    const FORMAT: &str = "%H:%M";
+
+    pub fn serialize<S: Serializer>(time: &NaiveTime, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&time.format(FORMAT).to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveTime, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        NaiveTime::parse_from_str(&value, FORMAT).map_err(|_| {
+            serde::de::Error::custom(format!(
+                "expected a time of day like \"04:00\", got \"{}\"",
+                value
+            ))
+        })
+    }
+}
+
+/// Expand a leading `~` to the home directory
+fn expand_home(path: &Path) -> PathBuf {
+    match (path.strip_prefix("~"), dirs::home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path.to_path_buf(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_discovery_roots_expand_home() {
+        let home = dirs::home_dir().unwrap();
+        let config = Config {
+            discovery: DiscoveryConfig {
+                roots: vec![
+                    PathBuf::from("~/code"),
+                    PathBuf::from("~"),
+                    PathBuf::from("/srv/repos"),
+                    PathBuf::from("~other/code"),
+                ],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.discovery_options().roots,
+            vec![
+                home.join("code"),
+                home.clone(),
+                PathBuf::from("/srv/repos"),
+                // Other users' home directories aren't expanded
+                PathBuf::from("~other/code"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_settings_are_rejected() {
+        let path = Path::new("config.toml");
+        let error = |contents: &str| format!("{:#}", Config::parse(path, contents).unwrap_err());
+
+        assert!(error("[happiness]\nfull_at_commits = 0\n")
+            .contains("happiness.full_at_commits must be at least 1"));
+        assert!(error("[behavior]\nautosave_secs = 0\n").contains("behavior.autosave_secs"));
+        assert!(error(
+            "[schedule]\nweekend_days = [\"Mon\", \"Tue\", \"Wed\", \"Thu\", \"Fri\", \"Sat\", \"Sun\"]\n"
+        )
+        .contains("schedule.weekend_days must leave at least one day"));
+        assert!(error("[happiness]\nfull_at = 5\n").contains("unknown field `full_at`"));
+        assert!(error("[schedule]\nday_starts_at = \"23:00\"\n")
+            .contains("schedule.day_starts_at must be before 12:00"));
+        assert!(error("[discovery]\nignore = [\"[\"]\n").contains("Invalid pattern '['"));
+
+        let config =
+            Config::parse(path, "[schedule]\nweekend_days = [\"Fri\", \"Sat\"]\n").unwrap();
+        assert_eq!(
+            config.rules().weekend_days,
+            vec![Weekday::Fri, Weekday::Sat]
+        );
+    }
+
+    #[test]
+    fn test_values_show_where_settings_come_from() {
+        let contents = "backfill_days = 7\n[schedule]\nweekend_days = [\"Sun\"]\n";
+        let mut config = Config::parse(Path::new("config.toml"), contents).unwrap();
+        config.cli_repos = vec![PathBuf::from("/srv/repo")];
+        let values = config
+            .values_from(&toml::from_str(contents).unwrap())
+            .unwrap();
+        let value = |key: &str| values.iter().find(|v| v.key == key).unwrap();
+
+        assert_eq!(value("backfill_days").value, "7");
+        assert_eq!(value("backfill_days").source, ValueSource::File);
+        assert_eq!(value("schedule.weekend_days").value, "[\"Sun\"]");
+        assert_eq!(value("schedule.weekend_days").source, ValueSource::File);
+        assert_eq!(value("schedule.day_starts_at").value, "\"00:00\"");
+        assert_eq!(value("schedule.day_starts_at").source, ValueSource::Default);
+        assert_eq!(value("watch.mode").source, ValueSource::Default);
+        assert_eq!(value("--repo").source, ValueSource::CommandLine);
+    }
+}
@@ -1,5 +1,6 @@
 use super::mood::Mood;
 use rand::Rng;
+use std::collections::HashMap;
 
 /// Physics constants
 const GRAVITY: f32 = 0.1;
@@ -12,6 +13,9 @@ const JUMP_STRENGTH_ECSTATIC: f32 = 1.8;
 const JUMP_STRENGTH_HAPPY: f32 = 1.4;
 const JUMP_STRENGTH_NEUTRAL: f32 = 1.0;
 
+/// How fast anxiety moves toward its target (per second)
+const ANXIETY_DRIFT_RATE: f32 = 0.02;
+
 /// Direction the crab is facing
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Direction {
@@ -26,6 +30,7 @@ impl Eyes {
     pub const NEUTRAL: &'static str = "o o";
     pub const HAPPY: &'static str = "^ ^";
     pub const SAD: &'static str = "- -";
+    pub const ANXIOUS: &'static str = "O O";
     pub const HUNGRY: &'static str = "T T";
     pub const ECSTATIC: &'static str = "* *";
 }
@@ -37,6 +42,7 @@ impl Mouths {
     pub const NEUTRAL: &'static str = "-";
     pub const HAPPY: &'static str = "u";
     pub const SAD: &'static str = "n";
+    pub const ANXIOUS: &'static str = "s";
     pub const HUNGRY: &'static str = "~";
     pub const ECSTATIC: &'static str = "w";
 }
@@ -106,6 +112,98 @@ impl BodyTemplates {
   // '-----' \\"#;
 }
 
+/// Shell pattern shared by every body template, swapped out by accessories
+const SHELL_PATTERN: &str = "~^~^~";
+
+/// A cosmetic accessory Kani unlocks after enough commits in one language
+#[derive(Debug, PartialEq, Eq)]
+pub struct Accessory {
+    /// Display name
+    pub name: &'static str,
+    /// Language whose commits unlock it
+    pub language: &'static str,
+    /// Commits touching the language needed to unlock it
+    pub commits: u32,
+    /// Replacement for the shell pattern (same width)
+    pub shell: Option<&'static str>,
+    /// Shell color used instead of the mood color
+    pub tint: Option<(u8, u8, u8)>,
+}
+
+/// Every accessory; when several change the same thing, the first one in this list wins
+pub const WARDROBE: &[Accessory] = &[
+    Accessory {
+        name: "Oxidized shell",
+        language: "Rust",
+        commits: 100,
+        shell: None,
+        tint: Some((206, 92, 38)),
+    },
+    Accessory {
+        name: "Gopher-blue shell",
+        language: "Go",
+        commits: 100,
+        shell: None,
+        tint: Some((0, 173, 216)),
+    },
+    Accessory {
+        name: "Scaly shell",
+        language: "Python",
+        commits: 100,
+        shell: Some("≈~≈~≈"),
+        tint: None,
+    },
+    Accessory {
+        name: "Curly shell",
+        language: "JavaScript",
+        commits: 100,
+        shell: Some("{~^~}"),
+        tint: None,
+    },
+    Accessory {
+        name: "Typed shell",
+        language: "TypeScript",
+        commits: 100,
+        shell: Some("<~T~>"),
+        tint: None,
+    },
+    Accessory {
+        name: "Pointer spikes",
+        language: "C++",
+        commits: 100,
+        shell: Some("*^*^*"),
+        tint: None,
+    },
+    Accessory {
+        name: "Lambda crest",
+        language: "Haskell",
+        commits: 50,
+        shell: Some("~λ~λ~"),
+        tint: None,
+    },
+];
+
+/// Accessories unlocked by the given per-language commit totals
+pub fn unlocked_accessories(totals: &HashMap<String, u32>) -> Vec<&'static Accessory> {
+    WARDROBE
+        .iter()
+        .filter(|a| totals.get(a.language).copied().unwrap_or(0) >= a.commits)
+        .collect()
+}
+
+/// The locked accessory closest to being unlocked, with the commits made so far
+pub fn next_accessory(totals: &HashMap<String, u32>) -> Option<(&'static Accessory, u32)> {
+    WARDROBE
+        .iter()
+        .map(|a| (a, totals.get(a.language).copied().unwrap_or(0)))
+        .filter(|(a, done)| *done < a.commits)
+        .max_by(|(a, done_a), (b, done_b)| {
+            let progress_a = *done_a as f32 / a.commits as f32;
+            let progress_b = *done_b as f32 / b.commits as f32;
+            progress_a.total_cmp(&progress_b)
+        })
+}
+
 /// Helper to build a frame from a body template and face components
 pub fn build_frame(body: &str, eyes: &str, mouth: &str) -> String {
     body.replace("{eyes}", eyes).replace("{mouth}", mouth)
@@ -123,6 +221,10 @@ pub struct Crab {
     pub mood: Mood,
     /// Happiness level (0-100)
     pub happiness: u8,
+    /// Worry about uncommitted work (0.0-1.0), drifting toward `anxiety_target`
+    pub anxiety: f32,
+    /// How worried the crab should be given the working trees
+    anxiety_target: f32,
     /// Animation frame index
     frame_index: usize,
     /// Animation timer
@@ -141,8 +243,10 @@ pub struct Crab {
     ground_y: f32,
     /// Cooldown timer for jumping (prevents spam)
     jump_cooldown: f32,
-    /// Whether celebration jump has been triggered (once per celebration)
-    celebration_jump_done: bool,
+    /// Celebration jumps still to do (bigger events get more)
+    celebration_jumps_left: u8,
+    /// Unlocked accessories
+    wardrobe: Vec<&'static Accessory>,
 }
 
 impl Crab {
@@ -161,6 +265,8 @@ impl Crab {
             direction,
             mood: Mood::from_happiness(happiness),
             happiness,
+            anxiety: 0.0,
+            anxiety_target: 0.0,
             frame_index: 0,
             animation_timer: 0.0,
             celebrating: false,
@@ -170,14 +276,19 @@ impl Crab {
             is_grounded: true,
             ground_y: position.1, // Will be set properly on first update
             jump_cooldown: 0.0,
-            celebration_jump_done: false,
+            celebration_jumps_left: 0,
+            wardrobe: Vec::new(),
         }
     }
 
     /// Update the crab's animation and position
     pub fn update(&mut self, dt: f32, bounds: (f32, f32)) {
-        // Update mood from happiness
-        self.mood = Mood::from_happiness(self.happiness);
+        // Anxiety builds up and fades gradually rather than flipping the mood at once
+        let drift = ANXIETY_DRIFT_RATE * dt;
+        self.anxiety += (self.anxiety_target - self.anxiety).clamp(-drift, drift);
+
+        // Update mood from happiness and anxiety
+        self.mood = Mood::from_happiness(self.happiness).with_anxiety(self.anxiety);
 
         // Calculate ground position (leave 1 line space at bottom for ground decoration)
         let frame_height = 4.0;
@@ -195,7 +306,7 @@ impl Crab {
             self.celebration_timer -= dt;
             if self.celebration_timer <= 0.0 {
                 self.celebrating = false;
-                self.celebration_jump_done = false; // Reset for next celebration
+                self.celebration_jumps_left = 0; // Reset for next celebration
             }
         }
 
@@ -223,20 +334,20 @@ impl Crab {
             return;
         }
 
-        // Trigger celebration jump (once per celebration)
-        if self.celebrating && !self.celebration_jump_done && self.is_grounded {
+        // Trigger celebration jumps (one per landing until used up)
+        if self.celebrating && self.celebration_jumps_left > 0 && self.is_grounded {
             let strength = self.randomize_jump_strength(JUMP_STRENGTH_CELEBRATION);
             self.jump(strength);
-            self.celebration_jump_done = true;
+            self.celebration_jumps_left -= 1;
         }
 
         // Random jumps based on mood (only when grounded)
         if self.is_grounded && !self.celebrating {
             let jump_chance = match self.mood {
-                Mood::Ecstatic => 0.015,         // ~1.5% per tick
-                Mood::Happy => 0.004,            // ~0.4% per tick
-                Mood::Neutral => 0.001,          // ~0.1% per tick
-                Mood::Sad | Mood::Hungry => 0.0, // No jumping when sad/hungry
+                Mood::Ecstatic => 0.015,                         // ~1.5% per tick
+                Mood::Happy => 0.004,                            // ~0.4% per tick
+                Mood::Neutral => 0.001,                          // ~0.1% per tick
+                Mood::Anxious | Mood::Sad | Mood::Hungry => 0.0, // Too worried or sad to jump
             };
 
             if self.rng.gen::<f32>() < jump_chance {
@@ -258,6 +369,7 @@ impl Crab {
             Mood::Ecstatic => 0.05,
             Mood::Happy => 0.03,
             Mood::Neutral => 0.02,
+            Mood::Anxious => 0.06, // Pacing back and forth
             Mood::Sad => 0.01,
             Mood::Hungry => 0.005,
         };
@@ -268,6 +380,7 @@ impl Crab {
                 Mood::Ecstatic => 1.5,
                 Mood::Happy => 1.0,
                 Mood::Neutral => 0.5,
+                Mood::Anxious => 0.8,
                 Mood::Sad => 0.3,
                 Mood::Hungry => 0.1,
             };
@@ -343,8 +456,30 @@ impl Crab {
         (base * variance).max(0.7)
     }
 
+    /// Dress the crab for the given per-language commit totals
+    /// Returns the accessories that weren't worn before
+    pub fn set_wardrobe(&mut self, totals: &HashMap<String, u32>) -> Vec<&'static Accessory> {
+        let unlocked = unlocked_accessories(totals);
+        let new = unlocked
+            .iter()
+            .filter(|a| !self.wardrobe.contains(a))
+            .copied()
+            .collect();
+        self.wardrobe = unlocked;
+        new
+    }
+
     /// Get the current animation frame as a string
     pub fn get_frame(&self) -> String {
+        let frame = self.get_body_frame();
+        match self.wardrobe.iter().find_map(|a| a.shell) {
+            Some(shell) => frame.replace(SHELL_PATTERN, shell),
+            None => frame,
+        }
+    }
+
+    /// Get the animation frame before accessories are applied
+    fn get_body_frame(&self) -> String {
         let is_moving = self.velocity.0.abs() > 0.05;
         let is_jumping = !self.is_grounded;
 
@@ -363,6 +498,7 @@ impl Crab {
             Mood::Ecstatic => (Eyes::ECSTATIC, Mouths::ECSTATIC),
             Mood::Happy => (Eyes::HAPPY, Mouths::HAPPY),
             Mood::Neutral => (Eyes::NEUTRAL, Mouths::NEUTRAL),
+            Mood::Anxious => (Eyes::ANXIOUS, Mouths::ANXIOUS),
             Mood::Sad => (Eyes::SAD, Mouths::SAD),
             Mood::Hungry => (Eyes::HUNGRY, Mouths::HUNGRY),
         };
@@ -404,7 +540,7 @@ impl Crab {
                     BodyTemplates::STANDING_LEFT
                 }
             }
-            Mood::Neutral => {
+            Mood::Neutral | Mood::Anxious => {
                 if is_moving {
                     if self.direction == Direction::Right {
                         if self.frame_index % 2 == 0 {
@@ -450,10 +586,21 @@ impl Crab {
         build_frame(body, eyes, mouth)
     }
 
+    /// Set how worried the crab should become (0.0-1.0); the mood follows gradually
+    pub fn set_anxiety_target(&mut self, target: f32) {
+        self.anxiety_target = target.clamp(0.0, 1.0);
+    }
+
     /// Trigger celebration (e.g., when a new commit is detected)
     pub fn celebrate(&mut self) {
+        self.celebrate_with(3.0, 1); // 3 seconds of celebration
+    }
+
+    /// Trigger a celebration lasting `seconds` with a number of jumps
+    pub fn celebrate_with(&mut self, seconds: f32, jumps: u8) {
         self.celebrating = true;
-        self.celebration_timer = 3.0; // 3 seconds of celebration
+        self.celebration_timer = seconds;
+        self.celebration_jumps_left = jumps;
     }
 
     /// Get the crab's color based on mood
@@ -461,12 +608,15 @@ impl Crab {
         use ratatui::style::Color;
         if self.celebrating {
             Color::LightMagenta
+        } else if let Some((r, g, b)) = self.wardrobe.iter().find_map(|a| a.tint) {
+            Color::Rgb(r, g, b)
         } else {
             // Reddish-orange crab color, slightly adjusted by mood
             match self.mood {
                 Mood::Ecstatic => Color::Rgb(255, 100, 100),
                 Mood::Happy => Color::Rgb(255, 120, 80),
                 Mood::Neutral => Color::Rgb(220, 100, 80),
+                Mood::Anxious => Color::Rgb(230, 140, 70),
                 Mood::Sad => Color::Rgb(180, 80, 80),
                 Mood::Hungry => Color::Rgb(150, 60, 60),
             }
@@ -1,5 +1,5 @@
 mod entity;
 mod mood;
 
-pub use entity::Crab;
+pub use entity::{next_accessory, unlocked_accessories, Crab};
 pub use mood::Mood;
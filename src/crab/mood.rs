@@ -9,6 +9,8 @@ pub enum Mood {
     Happy,
     /// Some activity recently - slower movement
     Neutral,
+    /// Too much uncommitted work for too long - pacing nervously
+    Anxious,
     /// No commits today - droopy, minimal movement
     Sad,
     /// Long idle period - dramatic sad pose, begging
@@ -27,12 +29,23 @@ impl Mood {
         }
     }
 
+    /// Mood once anxiety (0.0-1.0) about uncommitted work is taken into account
+    /// Past the halfway mark, worry wins over whatever the happiness says.
+    pub fn with_anxiety(self, anxiety: f32) -> Self {
+        if anxiety >= 0.5 {
+            Mood::Anxious
+        } else {
+            self
+        }
+    }
+
     /// Get a display name for the mood
     pub fn display_name(&self) -> &'static str {
         match self {
             Mood::Ecstatic => "Ecstatic",
             Mood::Happy => "Happy",
             Mood::Neutral => "Neutral",
+            Mood::Anxious => "Anxious",
             Mood::Sad => "Sad",
             Mood::Hungry => "Hungry",
         }
@@ -45,6 +58,7 @@ impl Mood {
             Mood::Ecstatic => Color::Magenta,
             Mood::Happy => Color::Green,
             Mood::Neutral => Color::Yellow,
+            Mood::Anxious => Color::LightRed,
             Mood::Sad => Color::Blue,
             Mood::Hungry => Color::Red,
         }
@@ -56,6 +70,7 @@ impl Mood {
             Mood::Ecstatic => 2.0, // Fast happy dance
             Mood::Happy => 1.0,    // Normal speed
             Mood::Neutral => 0.6,  // Slower
+            Mood::Anxious => 1.5,  // Fidgeting
             Mood::Sad => 0.3,      // Very slow
             Mood::Hungry => 0.2,   // Barely moving
         }
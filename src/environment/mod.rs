@@ -102,6 +102,49 @@ pub struct Cloud {
     pub night_visible: bool,
 }
 
+/// A firework rocket: rises from the ground, then bursts into a ring of sparks
+#[derive(Debug, Clone)]
+pub struct Firework {
+    /// Horizontal position of the burst
+    pub x: f32,
+    /// Height of the burst (rows from the top)
+    pub burst_y: f32,
+    /// Seconds since launch (negative while waiting to launch)
+    pub age: f32,
+    /// Index into the firework color palette
+    pub color: usize,
+}
+
+/// Seconds a firework rocket takes to reach its burst height
+pub const FIREWORK_RISE_TIME: f32 = 0.8;
+/// Seconds the burst stays visible
+pub const FIREWORK_BURST_TIME: f32 = 1.4;
+
+impl Firework {
+    /// Current rocket position while rising (None once it has burst or before launch)
+    pub fn rocket_position(&self, ground_y: f32) -> Option<(f32, f32)> {
+        if self.age < 0.0 || self.age >= FIREWORK_RISE_TIME {
+            return None;
+        }
+        let t = self.age / FIREWORK_RISE_TIME;
+        Some((self.x, ground_y - (ground_y - self.burst_y) * t))
+    }
+
+    /// Burst radius and progress (0.0 to 1.0), or None if not bursting
+    pub fn burst(&self) -> Option<(f32, f32)> {
+        let burst_age = self.age - FIREWORK_RISE_TIME;
+        if !(0.0..FIREWORK_BURST_TIME).contains(&burst_age) {
+            return None;
+        }
+        let progress = burst_age / FIREWORK_BURST_TIME;
+        Some((1.0 + progress * 4.0, progress))
+    }
+
+    fn is_done(&self) -> bool {
+        self.age >= FIREWORK_RISE_TIME + FIREWORK_BURST_TIME
+    }
+}
+
 /// The complete environment state
 #[derive(Debug, Clone)]
 pub struct Environment {
@@ -123,6 +166,8 @@ pub struct Environment {
     pub cycle_phase: f32,
     /// Total cycle duration
     pub cycle_duration: Duration,
+    /// Fireworks currently in the sky (release celebrations)
+    pub fireworks: Vec<Firework>,
 }
 
 impl Environment {
@@ -156,6 +201,7 @@ impl Environment {
             time_of_day,
             cycle_phase,
             cycle_duration,
+            fireworks: Vec::new(),
         }
     }
 
@@ -273,6 +319,31 @@ impl Environment {
         }
     }
 
+    /// Launch a volley of fireworks, staggered so they don't all burst at once
+    pub fn launch_fireworks(&mut self, count: usize) {
+        if self.width < 10 || self.height < 6 {
+            return;
+        }
+
+        let mut rng = rand::thread_rng();
+        for i in 0..count {
+            self.fireworks.push(Firework {
+                x: rng.gen_range(4.0..(self.width as f32 - 4.0)),
+                burst_y: rng.gen_range(1.0..(self.height as f32 / 3.0).max(2.0)),
+                age: -(i as f32) * rng.gen_range(0.3..0.6),
+                color: rng.gen_range(0..6),
+            });
+        }
+    }
+
+    /// Advance fireworks and drop the ones that have faded
+    pub fn update_fireworks(&mut self, dt: f32) {
+        for firework in &mut self.fireworks {
+            firework.age += dt;
+        }
+        self.fireworks.retain(|firework| !firework.is_done());
+    }
+
     pub fn sun_position(&self) -> Option<(i32, i32)> {
         if self.cycle_phase >= 0.5 {
             return None;
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+/// A commit message parsed as a Conventional Commit (`type(scope)!: description`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConventionalCommit {
+    /// Commit type, lowercased (e.g. "feat", "fix", "docs")
+    pub commit_type: String,
+    /// Optional scope between parentheses
+    #[serde(default)]
+    pub scope: Option<String>,
+    /// Whether the commit is marked as a breaking change
+    #[serde(default)]
+    pub breaking: bool,
+}
+
+/// Parse a full commit message as a Conventional Commit
+/// Git's own `Revert "..."` subjects are treated as the `revert` type.
+/// Returns None when the subject doesn't follow the convention.
+pub fn parse_conventional(message: &str) -> Option<ConventionalCommit> {
+    let mut lines = message.lines();
+    let subject = lines.next()?.trim();
+
+    if subject.starts_with("Revert \"") {
+        return Some(ConventionalCommit {
+            commit_type: "revert".to_string(),
+            scope: None,
+            breaking: false,
+        });
+    }
+
+    let (header, description) = subject.split_once(':')?;
+    if description.trim().is_empty() {
+        return None;
+    }
+
+    let (header, bang) = match header.strip_suffix('!') {
+        Some(header) => (header, true),
+        None => (header, false),
+    };
+    let (commit_type, scope) = match header.split_once('(') {
+        Some((commit_type, rest)) => {
+            let scope = rest.strip_suffix(')')?;
+            if scope.is_empty() || scope.contains(['(', ')']) {
+                return None;
+            }
+            (commit_type, Some(scope.to_string()))
+        }
+        None => (header, None),
+    };
+    if commit_type.is_empty() || !commit_type.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    // A BREAKING CHANGE footer also marks the commit as breaking
+    let footer_breaking = lines
+        .any(|line| line.starts_with("BREAKING CHANGE:") || line.starts_with("BREAKING-CHANGE:"));
+
+    Some(ConventionalCommit {
+        commit_type: commit_type.to_ascii_lowercase(),
+        scope,
+        breaking: bang || footer_breaking,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parsed(message: &str) -> Option<(String, Option<String>, bool)> {
+        parse_conventional(message).map(|c| (c.commit_type, c.scope, c.breaking))
+    }
+
+    #[test]
+    fn test_parse_type_and_scope() {
+        assert_eq!(
+            parsed("fix: handle empty repos"),
+            Some(("fix".to_string(), None, false))
+        );
+        assert_eq!(
+            parsed("feat(ui): add heatmap"),
+            Some(("feat".to_string(), Some("ui".to_string()), false))
+        );
+        assert_eq!(
+            parsed("Docs: typo"),
+            Some(("docs".to_string(), None, false))
+        );
+    }
+
+    #[test]
+    fn test_parse_breaking_changes() {
+        assert_eq!(
+            parsed("feat!: drop old config"),
+            Some(("feat".to_string(), None, true))
+        );
+        assert_eq!(
+            parsed("refactor(state)!: new format"),
+            Some(("refactor".to_string(), Some("state".to_string()), true))
+        );
+        assert_eq!(
+            parsed("feat: new format\n\nBREAKING CHANGE: old files are ignored"),
+            Some(("feat".to_string(), None, true))
+        );
+    }
+
+    #[test]
+    fn test_parse_git_revert() {
+        assert_eq!(
+            parsed("Revert \"feat: add heatmap\"\n\nThis reverts commit abc."),
+            Some(("revert".to_string(), None, false))
+        );
+    }
+
+    #[test]
+    fn test_non_conventional_subjects() {
+        assert_eq!(parsed("Add recursive discovery"), None);
+        assert_eq!(parsed("fix:"), None);
+        assert_eq!(parsed("see http://example.com: it broke"), None);
+        assert_eq!(parsed("feat(: oops"), None);
+        assert_eq!(parsed("[user-001] Count commits: all of them"), None);
+    }
+}
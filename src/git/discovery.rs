@@ -0,0 +1,374 @@
+use git2::Repository;
+use glob::Pattern;
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Where a tracked repository was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RepoSource {
+    /// Kanitomo was started inside this repository
+    CurrentDir,
+    /// Found by scanning below the current directory
+    CurrentDirScan,
+    /// Found by scanning a watch root from the config file
+    WatchRoot(PathBuf),
+    /// Passed explicitly with `--repo`
+    CommandLine,
+    /// A linked worktree of the named repository
+    Worktree(String),
+    /// A submodule of the named repository
+    Submodule(String),
+}
+
+impl fmt::Display for RepoSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RepoSource::CurrentDir => write!(f, "current dir"),
+            RepoSource::CurrentDirScan => write!(f, "scan of ."),
+            RepoSource::WatchRoot(root) => write!(f, "root {}", root.display()),
+            RepoSource::CommandLine => write!(f, "--repo"),
+            RepoSource::Worktree(main) => write!(f, "worktree of {}", main),
+            RepoSource::Submodule(parent) => write!(f, "submodule of {}", parent),
+        }
+    }
+}
+
+/// Settings controlling where repositories are discovered
+#[derive(Debug, Clone)]
+pub struct DiscoveryOptions {
+    /// Repositories passed explicitly (skips scanning the current directory)
+    pub repos: Vec<PathBuf>,
+    /// Extra directories to scan for repositories
+    pub roots: Vec<PathBuf>,
+    /// How many directory levels below a scan root to descend
+    pub max_depth: usize,
+    /// Glob patterns for directory names (or root-relative paths) to skip
+    pub ignore: Vec<String>,
+    /// Whether to track the submodules of discovered repositories
+    pub submodules: bool,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            roots: Vec::new(),
+            max_depth: 3,
+            ignore: default_ignore(),
+            submodules: true,
+        }
+    }
+}
+
+/// Directories that never contain repositories worth watching
+pub fn default_ignore() -> Vec<String> {
+    ["node_modules", "target", "vendor"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Result of a discovery pass
+pub struct Discovery {
+    /// Repositories found, sorted by name
+    pub repos: Vec<(Repository, RepoSource)>,
+    /// Directories that were scanned (new repositories show up as changes in these)
+    pub scanned_dirs: Vec<PathBuf>,
+}
+
+/// How deep the current directory is scanned when Kanitomo isn't started in a repository
+/// (`max_depth` only applies to configured roots: starting in `$HOME` shouldn't crawl it)
+const CURRENT_DIR_SCAN_DEPTH: usize = 1;
+
+/// Discover git repositories
+/// Explicit repos are used as-is; without them, the current repo is used if we're
+/// inside one, otherwise the current directory is scanned. Watch roots are always scanned.
+pub fn discover_repos(options: &DiscoveryOptions) -> Discovery {
+    discover_repos_from(options, Path::new("."))
+}
+
+/// Discover git repositories as if Kanitomo was started in `current_dir`
+fn discover_repos_from(options: &DiscoveryOptions, current_dir: &Path) -> Discovery {
+    let mut scanner = Scanner {
+        submodules: options.submodules,
+        ignore: options
+            .ignore
+            .iter()
+            .filter_map(|pattern| Pattern::new(pattern).ok())
+            .collect(),
+        seen: HashSet::new(),
+        found: Vec::new(),
+        scanned_dirs: Vec::new(),
+    };
+
+    for path in &options.repos {
+        if let Ok(repo) = Repository::discover(path) {
+            scanner.add(repo, RepoSource::CommandLine);
+        }
+    }
+
+    if options.repos.is_empty() {
+        match Repository::discover(current_dir) {
+            Ok(repo) => scanner.add(repo, RepoSource::CurrentDir),
+            Err(_) => scanner.scan(
+                current_dir,
+                CURRENT_DIR_SCAN_DEPTH,
+                &RepoSource::CurrentDirScan,
+            ),
+        }
+    }
+
+    for root in &options.roots {
+        scanner.scan(
+            root,
+            options.max_depth,
+            &RepoSource::WatchRoot(root.clone()),
+        );
+    }
+
+    let mut repos = scanner.found;
+
+    // Sort by repo name for consistent ordering
+    repos.sort_by(|(a, _), (b, _)| {
+        let name_a = a.workdir().and_then(|p| p.file_name());
+        let name_b = b.workdir().and_then(|p| p.file_name());
+        name_a.cmp(&name_b)
+    });
+
+    Discovery {
+        repos,
+        scanned_dirs: scanner.scanned_dirs,
+    }
+}
+
+/// The git directory shared by all worktrees of a repository
+pub fn common_dir(repo: &Repository) -> PathBuf {
+    // Linked worktrees point at the main git dir through a `commondir` file
+    match std::fs::read_to_string(repo.path().join("commondir")) {
+        Ok(contents) => repo.path().join(contents.trim()),
+        Err(_) => repo.path().to_path_buf(),
+    }
+}
+
+/// Recursive directory walker collecting repositories
+struct Scanner {
+    submodules: bool,
+    ignore: Vec<Pattern>,
+    /// Canonical git dirs already found (a repo may sit under several roots)
+    seen: HashSet<PathBuf>,
+    found: Vec<(Repository, RepoSource)>,
+    /// Every directory read while scanning
+    scanned_dirs: Vec<PathBuf>,
+}
+
+impl Scanner {
+    fn add(&mut self, repo: Repository, source: RepoSource) {
+        let key = repo
+            .path()
+            .canonicalize()
+            .unwrap_or_else(|_| repo.path().to_path_buf());
+        if !self.seen.insert(key) {
+            return;
+        }
+
+        let name = repo
+            .workdir()
+            .and_then(|p| p.file_name())
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        // Each linked worktree has its own HEAD to watch
+        if let Ok(names) = repo.worktrees() {
+            for worktree in names.iter().flatten() {
+                let Ok(worktree) = repo.find_worktree(worktree) else {
+                    continue;
+                };
+                if worktree.validate().is_err() {
+                    continue; // Pruned or moved
+                }
+                if let Ok(worktree_repo) = Repository::open_from_worktree(&worktree) {
+                    self.add(worktree_repo, RepoSource::Worktree(name.clone()));
+                }
+            }
+        }
+
+        if self.submodules {
+            if let Ok(submodules) = repo.submodules() {
+                for submodule in submodules {
+                    // Uninitialized submodules have nothing to open
+                    if let Ok(submodule_repo) = submodule.open() {
+                        self.add(submodule_repo, RepoSource::Submodule(name.clone()));
+                    }
+                }
+            }
+        }
+
+        self.found.push((repo, source));
+    }
+
+    /// Scan `root` for repositories, descending at most `max_depth` levels
+    fn scan(&mut self, root: &Path, max_depth: usize, source: &RepoSource) {
+        // Absolute paths so scanned dirs line up with file watcher events
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        self.scan_dir(&root, &root, 1, max_depth, source);
+    }
+
+    fn scan_dir(
+        &mut self,
+        root: &Path,
+        dir: &Path,
+        depth: usize,
+        max_depth: usize,
+        source: &RepoSource,
+    ) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        self.scanned_dirs.push(dir.to_path_buf());
+
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            // Hidden directories (`.git`, `.cache`, `.cargo`...) hold no projects
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if hidden || !path.is_dir() || self.is_ignored(root, &path) {
+                continue;
+            }
+
+            // A repository ends the descent (submodules are handled separately)
+            if path.join(".git").exists() {
+                if let Ok(repo) = Repository::open(&path) {
+                    self.add(repo, source.clone());
+                    continue;
+                }
+            }
+
+            if depth < max_depth {
+                self.scan_dir(root, &path, depth + 1, max_depth, source);
+            }
+        }
+    }
+
+    fn is_ignored(&self, root: &Path, path: &Path) -> bool {
+        let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let relative = path.strip_prefix(root).unwrap_or(path);
+
+        self.ignore
+            .iter()
+            .any(|pattern| pattern.matches(name) || pattern.matches_path(relative))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a repository at `relative` below `root`
+    fn init_at(root: &Path, relative: &str) {
+        let path = root.join(relative);
+        fs::create_dir_all(&path).unwrap();
+        Repository::init(&path).unwrap();
+    }
+
+    /// Names of the repositories found below `root`
+    fn found_below(root: &TempDir, options: DiscoveryOptions) -> Vec<String> {
+        names_below(root, discover_repos(&options))
+    }
+
+    /// Names of the repositories in `discovery` that sit below `root`
+    fn names_below(root: &TempDir, discovery: Discovery) -> Vec<String> {
+        let root = root.path().canonicalize().unwrap();
+        let mut names: Vec<String> = discovery
+            .repos
+            .iter()
+            .filter_map(|(repo, _)| repo.workdir()?.canonicalize().ok())
+            .filter_map(|path| {
+                let relative = path.strip_prefix(&root).ok()?;
+                Some(relative.display().to_string())
+            })
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn scanning(root: &TempDir) -> DiscoveryOptions {
+        DiscoveryOptions {
+            roots: vec![root.path().to_path_buf()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scan_stops_at_max_depth() {
+        let root = TempDir::new().unwrap();
+        for repo in ["a", "x/b", "x/y/c", "x/y/z/d"] {
+            init_at(root.path(), repo);
+        }
+
+        assert_eq!(
+            found_below(&root, scanning(&root)),
+            vec!["a", "x/b", "x/y/c"]
+        );
+        let shallow = DiscoveryOptions {
+            max_depth: 1,
+            ..scanning(&root)
+        };
+        assert_eq!(found_below(&root, shallow), vec!["a"]);
+    }
+
+    #[test]
+    fn test_scan_does_not_descend_into_repositories() {
+        let root = TempDir::new().unwrap();
+        init_at(root.path(), "outer");
+        init_at(root.path(), "outer/vendored");
+
+        assert_eq!(found_below(&root, scanning(&root)), vec!["outer"]);
+    }
+
+    #[test]
+    fn test_scan_skips_ignored_directories() {
+        let root = TempDir::new().unwrap();
+        for repo in ["app", "node_modules/pkg", "archive/old", "tmp-scratch"] {
+            init_at(root.path(), repo);
+        }
+
+        // Default patterns match directory names at any depth
+        assert_eq!(
+            found_below(&root, scanning(&root)),
+            vec!["app", "archive/old", "tmp-scratch"]
+        );
+
+        // Patterns can also match paths relative to the root
+        let options = DiscoveryOptions {
+            ignore: vec!["archive/*".to_string(), "tmp-*".to_string()],
+            ..scanning(&root)
+        };
+        assert_eq!(found_below(&root, options), vec!["app", "node_modules/pkg"]);
+    }
+
+    #[test]
+    fn test_scan_skips_hidden_directories() {
+        let root = TempDir::new().unwrap();
+        for repo in ["app", ".cache/pkg", "code/.cargo/registry", "code/lib"] {
+            init_at(root.path(), repo);
+        }
+
+        assert_eq!(found_below(&root, scanning(&root)), vec!["app", "code/lib"]);
+    }
+
+    #[test]
+    fn test_current_dir_scan_only_looks_at_children() {
+        let root = TempDir::new().unwrap();
+        for repo in ["app", "code/lib", ".local/share/tool"] {
+            init_at(root.path(), repo);
+        }
+
+        // `max_depth` is for configured roots; starting in $HOME mustn't crawl it
+        let discovery = discover_repos_from(&DiscoveryOptions::default(), root.path());
+        assert_eq!(discovery.scanned_dirs.len(), 1);
+        assert_eq!(names_below(&root, discovery), vec!["app"]);
+    }
+}
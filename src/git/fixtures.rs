@@ -0,0 +1,74 @@
+use git2::{Oid, Repository, Signature};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A repository in a temporary directory, with the test author's email configured
+pub fn init_repo() -> (TempDir, Repository) {
+    let dir = TempDir::new().unwrap();
+    let repo = Repository::init(dir.path()).unwrap();
+    repo.config()
+        .unwrap()
+        .set_str("user.email", "me@example.com")
+        .unwrap();
+    (dir, repo)
+}
+
+/// The test author, matching the email configured by [`init_repo`]
+pub fn signature() -> Signature<'static> {
+    Signature::now("Me", "me@example.com").unwrap()
+}
+
+/// Create an empty commit on top of `parent` and point `update_ref` at it
+pub fn commit_on(
+    repo: &Repository,
+    update_ref: Option<&str>,
+    parent: Option<Oid>,
+    message: &str,
+) -> Oid {
+    let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+    commit_tree(repo, update_ref, tree_id, parent, message)
+}
+
+/// Write files into the working tree and commit them all on top of HEAD
+pub fn commit_files(repo: &Repository, files: &[(&str, &str)]) -> Oid {
+    let workdir = repo.workdir().unwrap();
+    let mut index = repo.index().unwrap();
+    for (name, content) in files {
+        fs::write(workdir.join(name), content).unwrap();
+        index.add_path(Path::new(name)).unwrap();
+    }
+    index.write().unwrap();
+    let parent = repo.head().ok().and_then(|h| h.target());
+    commit_tree(
+        repo,
+        Some("HEAD"),
+        index.write_tree().unwrap(),
+        parent,
+        "commit",
+    )
+}
+
+fn commit_tree(
+    repo: &Repository,
+    update_ref: Option<&str>,
+    tree_id: Oid,
+    parent: Option<Oid>,
+    message: &str,
+) -> Oid {
+    let tree = repo.find_tree(tree_id).unwrap();
+    let parents: Vec<git2::Commit> = parent
+        .map(|oid| repo.find_commit(oid).unwrap())
+        .into_iter()
+        .collect();
+    let parent_refs: Vec<&git2::Commit> = parents.iter().collect();
+    repo.commit(
+        update_ref,
+        &signature(),
+        &signature(),
+        message,
+        &tree,
+        &parent_refs,
+    )
+    .unwrap()
+}
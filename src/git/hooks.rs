@@ -0,0 +1,280 @@
+use super::discovery::common_dir;
+use anyhow::{Context, Result};
+use git2::Repository;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Marker line identifying hooks written by Kanitomo
+const HOOK_MARKER: &str = "# kanitomo hook";
+
+/// Suffix given to a pre-existing hook that our hook chains to
+const CHAINED_SUFFIX: &str = ".kanitomo-orig";
+
+/// Git hooks Kanitomo can install
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GitHook {
+    /// Runs after every `git commit`
+    PostCommit,
+    /// Runs after `git commit --amend` and `git rebase`, with rewritten commits on stdin
+    PostRewrite,
+}
+
+impl GitHook {
+    /// All hooks, in install order
+    pub const ALL: [GitHook; 2] = [GitHook::PostCommit, GitHook::PostRewrite];
+
+    /// The hook's file name inside the hooks directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            GitHook::PostCommit => "post-commit",
+            GitHook::PostRewrite => "post-rewrite",
+        }
+    }
+
+    /// Parse a hook from its file name
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|hook| hook.file_name() == name)
+    }
+}
+
+/// Result of installing or removing a hook in one repository
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HookAction {
+    /// Hook was written (an existing hook, if any, is chained)
+    Installed { chained: bool },
+    /// Our hook was already present
+    AlreadyInstalled,
+    /// Our hook was removed (a chained hook, if any, is restored)
+    Removed { restored: bool },
+    /// No Kanitomo hook to remove
+    NotInstalled,
+}
+
+/// Install a hook into a repository, chaining to any hook that is already there
+pub fn install_hook(git_dir: &Path, hook: GitHook, exe: &Path) -> Result<HookAction> {
+    let hooks_dir = hooks_dir(git_dir)?;
+    fs::create_dir_all(&hooks_dir).context("Failed to create hooks directory")?;
+
+    let hook_path = hooks_dir.join(hook.file_name());
+    let chained_path = chained_path(&hook_path);
+
+    if is_kanitomo_hook(&hook_path) {
+        return Ok(HookAction::AlreadyInstalled);
+    }
+
+    let chained = hook_path.exists();
+    if chained {
+        fs::rename(&hook_path, &chained_path)
+            .with_context(|| format!("Failed to move existing {}", hook.file_name()))?;
+    }
+
+    fs::write(&hook_path, hook_script(hook, exe))
+        .with_context(|| format!("Failed to write {}", hook_path.display()))?;
+    make_executable(&hook_path)?;
+
+    Ok(HookAction::Installed { chained })
+}
+
+/// Remove our hook from a repository, restoring a chained hook if there was one
+pub fn uninstall_hook(git_dir: &Path, hook: GitHook) -> Result<HookAction> {
+    let hook_path = hooks_dir(git_dir)?.join(hook.file_name());
+    let chained_path = chained_path(&hook_path);
+
+    if !is_kanitomo_hook(&hook_path) {
+        return Ok(HookAction::NotInstalled);
+    }
+
+    fs::remove_file(&hook_path)
+        .with_context(|| format!("Failed to remove {}", hook_path.display()))?;
+
+    let restored = chained_path.exists();
+    if restored {
+        fs::rename(&chained_path, &hook_path)
+            .with_context(|| format!("Failed to restore original {}", hook.file_name()))?;
+    }
+
+    Ok(HookAction::Removed { restored })
+}
+
+/// Parse the commit hashes a hook invocation is about
+/// `post-commit` reports HEAD; `post-rewrite` reads "<old> <new>" lines from stdin
+pub fn hook_commit_hashes(repo: &Repository, hook: GitHook, stdin: &str) -> Vec<String> {
+    match hook {
+        GitHook::PostCommit => repo
+            .head()
+            .ok()
+            .and_then(|head| head.target())
+            .map(|oid| vec![oid.to_string()])
+            .unwrap_or_default(),
+        GitHook::PostRewrite => stdin
+            .lines()
+            .filter_map(|line| line.split_whitespace().nth(1))
+            .map(|hash| hash.to_string())
+            .collect(),
+    }
+}
+
+/// Resolve the hooks directory, honouring `core.hooksPath`
+fn hooks_dir(git_dir: &Path) -> Result<PathBuf> {
+    let repo = Repository::open(git_dir)
+        .with_context(|| format!("Failed to open repository {}", git_dir.display()))?;
+
+    let configured = repo
+        .config()
+        .ok()
+        .and_then(|config| config.get_path("core.hooksPath").ok());
+
+    Ok(match configured {
+        Some(path) if path.is_absolute() => path,
+        Some(path) => repo.workdir().unwrap_or(repo.path()).join(path),
+        // Hooks are shared by all worktrees, so they live in the common dir
+        None => common_dir(&repo).join("hooks"),
+    })
+}
+
+fn chained_path(hook_path: &Path) -> PathBuf {
+    let mut name = hook_path.as_os_str().to_owned();
+    name.push(CHAINED_SUFFIX);
+    PathBuf::from(name)
+}
+
+fn is_kanitomo_hook(hook_path: &Path) -> bool {
+    fs::read_to_string(hook_path)
+        .map(|contents| contents.contains(HOOK_MARKER))
+        .unwrap_or(false)
+}
+
+/// Build the shell script for a hook
+fn hook_script(hook: GitHook, exe: &Path) -> String {
+    let name = hook.file_name();
+    let exe = shell_quote(&exe.display().to_string());
+
+    // post-rewrite gets the rewritten commits on stdin, which both the chained
+    // hook and Kanitomo need to read
+    let (capture, feed) = match hook {
+        GitHook::PostCommit => ("", ""),
+        GitHook::PostRewrite => ("input=$(cat)\n", "printf '%s\\n' \"$input\" | "),
+    };
+
+    format!(
+        "#!/bin/sh\n\
+         {HOOK_MARKER} (installed by `kanitomo hook install`)\n\
+         {capture}\
+         status=0\n\
+         chained=\"$(dirname \"$0\")/{name}{CHAINED_SUFFIX}\"\n\
+         if [ -x \"$chained\" ]; then\n    \
+             {feed}\"$chained\" \"$@\"\n    \
+             status=$?\n\
+         fi\n\
+         {feed}{exe} hook record {name} \"$@\" >/dev/null 2>&1 || true\n\
+         exit $status\n"
+    )
+}
+
+/// Quote a string for use in a POSIX shell script
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)?.permissions();
+    permissions.set_mode(0o755);
+    fs::set_permissions(path, permissions).context("Failed to make hook executable")
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const EXE: &str = "/usr/local/bin/kanitomo";
+
+    fn init_repo() -> (TempDir, PathBuf) {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        let git_dir = repo.path().to_path_buf();
+        (dir, git_dir)
+    }
+
+    #[test]
+    fn test_install_chains_existing_hook_and_uninstall_restores_it() {
+        let (_dir, git_dir) = init_repo();
+        let hook_path = git_dir.join("hooks/post-commit");
+        let original = "#!/bin/sh\necho original\n";
+        fs::create_dir_all(hook_path.parent().unwrap()).unwrap();
+        fs::write(&hook_path, original).unwrap();
+
+        let action = install_hook(&git_dir, GitHook::PostCommit, Path::new(EXE)).unwrap();
+        assert_eq!(action, HookAction::Installed { chained: true });
+        assert!(is_kanitomo_hook(&hook_path));
+        assert!(fs::read_to_string(&hook_path).unwrap().contains(EXE));
+        assert_eq!(
+            fs::read_to_string(chained_path(&hook_path)).unwrap(),
+            original
+        );
+
+        let action = uninstall_hook(&git_dir, GitHook::PostCommit).unwrap();
+        assert_eq!(action, HookAction::Removed { restored: true });
+        assert_eq!(fs::read_to_string(&hook_path).unwrap(), original);
+        assert!(!chained_path(&hook_path).exists());
+
+        let action = uninstall_hook(&git_dir, GitHook::PostCommit).unwrap();
+        assert_eq!(action, HookAction::NotInstalled);
+    }
+
+    #[test]
+    fn test_installing_twice_keeps_the_chained_hook() {
+        let (_dir, git_dir) = init_repo();
+        let exe = Path::new(EXE);
+
+        let first = install_hook(&git_dir, GitHook::PostRewrite, exe).unwrap();
+        assert_eq!(first, HookAction::Installed { chained: false });
+        let second = install_hook(&git_dir, GitHook::PostRewrite, exe).unwrap();
+        assert_eq!(second, HookAction::AlreadyInstalled);
+
+        // Our own hook must never end up chained to itself
+        let hook_path = git_dir.join("hooks/post-rewrite");
+        assert!(!chained_path(&hook_path).exists());
+        let action = uninstall_hook(&git_dir, GitHook::PostRewrite).unwrap();
+        assert_eq!(action, HookAction::Removed { restored: false });
+        assert!(!hook_path.exists());
+    }
+
+    #[test]
+    fn test_install_honours_core_hooks_path() {
+        let (dir, git_dir) = init_repo();
+        Repository::open(&git_dir)
+            .unwrap()
+            .config()
+            .unwrap()
+            .set_str("core.hooksPath", "custom-hooks")
+            .unwrap();
+
+        install_hook(&git_dir, GitHook::PostCommit, Path::new(EXE)).unwrap();
+        assert!(is_kanitomo_hook(
+            &dir.path().join("custom-hooks/post-commit")
+        ));
+        assert!(!git_dir.join("hooks/post-commit").exists());
+    }
+
+    #[test]
+    fn test_post_rewrite_hashes_come_from_stdin() {
+        let (_dir, git_dir) = init_repo();
+        let repo = Repository::open(&git_dir).unwrap();
+        let stdin = "1111 aaaa\n2222 bbbb extra\n\n";
+        assert_eq!(
+            hook_commit_hashes(&repo, GitHook::PostRewrite, stdin),
+            vec!["aaaa", "bbbb"]
+        );
+        // Nothing committed yet, so there is no HEAD to report
+        assert!(hook_commit_hashes(&repo, GitHook::PostCommit, "").is_empty());
+    }
+}
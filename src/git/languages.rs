@@ -0,0 +1,112 @@
+use git2::{AttrCheckFlags, AttrValue, Repository};
+use std::path::Path;
+
+/// Classify a file in a repository, honouring `.gitattributes` linguist attributes
+/// `linguist-language` overrides the extension; vendored, generated and
+/// documentation files aren't counted at all.
+pub fn language_for_file(repo: &Repository, path: &Path) -> Option<String> {
+    let flags = AttrCheckFlags::FILE_THEN_INDEX;
+    let is_set = |name: &str| {
+        repo.get_attr(path, name, flags)
+            .ok()
+            .is_some_and(|value| AttrValue::from_string(value) == AttrValue::True)
+    };
+    if is_set("linguist-vendored")
+        || is_set("linguist-generated")
+        || is_set("linguist-documentation")
+    {
+        return None;
+    }
+
+    let linguist = repo
+        .get_attr(path, "linguist-language", flags)
+        .ok()
+        .flatten()
+        .filter(|value| !value.is_empty());
+    match linguist {
+        Some(language) => Some(language.to_string()),
+        None => language_for_path(path).map(str::to_string),
+    }
+}
+
+/// Guess the language of a file from its extension (or well-known file name)
+pub fn language_for_path(path: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?;
+    match file_name {
+        "Dockerfile" => return Some("Docker"),
+        "Makefile" | "makefile" | "GNUmakefile" => return Some("Makefile"),
+        _ => {}
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let language = match extension.as_str() {
+        "rs" => "Rust",
+        "py" | "pyi" => "Python",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "mts" | "cts" | "tsx" => "TypeScript",
+        "go" => "Go",
+        "java" => "Java",
+        "kt" | "kts" => "Kotlin",
+        "scala" => "Scala",
+        "c" | "h" => "C",
+        "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => "C++",
+        "cs" => "C#",
+        "fs" | "fsx" => "F#",
+        "m" | "mm" => "Objective-C",
+        "swift" => "Swift",
+        "rb" => "Ruby",
+        "php" => "PHP",
+        "pl" | "pm" => "Perl",
+        "lua" => "Lua",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "hs" => "Haskell",
+        "ml" | "mli" => "OCaml",
+        "clj" | "cljs" | "edn" => "Clojure",
+        "zig" => "Zig",
+        "nim" => "Nim",
+        "dart" => "Dart",
+        "r" => "R",
+        "jl" => "Julia",
+        "sh" | "bash" | "zsh" | "fish" => "Shell",
+        "ps1" => "PowerShell",
+        "sql" => "SQL",
+        "html" | "htm" => "HTML",
+        "css" | "scss" | "sass" | "less" => "CSS",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "md" | "markdown" => "Markdown",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "yaml" | "yml" => "YAML",
+        "xml" => "XML",
+        "nix" => "Nix",
+        _ => return None,
+    };
+    Some(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_gitattributes_override_extension() {
+        let dir = TempDir::new().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(
+            dir.path().join(".gitattributes"),
+            "*.inc linguist-language=PHP\n*.h linguist-language=C++\nvendor/** linguist-vendored\n",
+        )
+        .unwrap();
+
+        let language = |path: &str| language_for_file(&repo, Path::new(path));
+        assert_eq!(language("src/main.rs").as_deref(), Some("Rust"));
+        assert_eq!(language("config.inc").as_deref(), Some("PHP"));
+        assert_eq!(language("include/lib.h").as_deref(), Some("C++"));
+        assert_eq!(language("vendor/lib/big.js"), None);
+        assert_eq!(language("notes.txt"), None);
+    }
+}
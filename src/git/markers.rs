@@ -0,0 +1,261 @@
+use git2::{Oid, Repository, TreeWalkMode, TreeWalkResult};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Words counted as leftover work markers
+const MARKERS: [&[u8]; 3] = [b"TODO", b"FIXME", b"HACK"];
+
+/// Blobs larger than this are skipped (generated files, vendored bundles)
+const MAX_BLOB_SIZE: usize = 1024 * 1024;
+
+/// Trees with more files than this aren't counted at all: the first scan reads every blob
+const MAX_SCANNED_FILES: usize = 20_000;
+
+/// TODO/FIXME/HACK markers in the tree of a commit
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MarkerCount {
+    /// Markers in the whole tree after the commit
+    pub total: u32,
+    /// Change compared to the previously scanned tree (negative when markers were removed)
+    /// Zero for the first scan of a repository
+    pub change: i32,
+}
+
+/// Count whole-word TODO/FIXME/HACK markers in a file's contents
+pub fn count_markers(content: &[u8]) -> u32 {
+    let is_word = |byte: u8| byte.is_ascii_alphanumeric() || byte == b'_';
+    let mut count = 0;
+
+    for (start, _) in content
+        .iter()
+        .enumerate()
+        .filter(|(_, b)| b.is_ascii_uppercase())
+    {
+        if start > 0 && is_word(content[start - 1]) {
+            continue;
+        }
+        for marker in MARKERS {
+            let end = start + marker.len();
+            if content.get(start..end) == Some(marker)
+                && !content.get(end).copied().is_some_and(is_word)
+            {
+                count += 1;
+            }
+        }
+    }
+
+    count
+}
+
+/// Marker counts per file at one commit, updated incrementally as HEAD moves
+/// Only files with at least one marker are kept.
+///
+/// Ignored files are skipped using the ignore rules on disk (`.gitignore` files in the
+/// working tree, `info/exclude` and the global excludes), not the ones in the scanned
+/// commit's tree: libgit2 can only match paths against the rules checked out. A commit
+/// that changes `.gitignore` is counted with whatever rules are on disk when it's scanned.
+#[derive(Debug, Clone, Default)]
+pub struct MarkerIndex {
+    /// Tree the counts were taken from (None before the first scan)
+    tree: Option<Oid>,
+    /// Whether the first scan gave up because the tree has too many files
+    too_large: bool,
+    /// Markers per file path
+    files: HashMap<PathBuf, u32>,
+    /// Sum of all per-file counts
+    total: u32,
+}
+
+impl MarkerIndex {
+    /// Move the index to a commit's tree, re-reading only the blobs that changed
+    /// The first call scans the whole tree. Returns the new count, or None if
+    /// the commit can't be read (the index is left untouched) or the tree is too
+    /// large to scan (the repository is never counted).
+    pub fn advance(&mut self, repo: &Repository, commit: Oid) -> Option<MarkerCount> {
+        self.advance_within(repo, commit, MAX_SCANNED_FILES)
+    }
+
+    /// Build the index at HEAD of repositories (by git dir), opening each one afresh
+    /// so the first full scans can run on a background thread
+    pub fn build_all(git_dirs: &[PathBuf]) -> Vec<(PathBuf, Self)> {
+        git_dirs
+            .iter()
+            .filter_map(|git_dir| {
+                let repo = Repository::open(git_dir).ok()?;
+                let head = repo.head().ok()?.target()?;
+                let mut index = Self::default();
+                index.advance(&repo, head);
+                Some((git_dir.clone(), index))
+            })
+            .collect()
+    }
+
+    fn advance_within(
+        &mut self,
+        repo: &Repository,
+        commit: Oid,
+        max_files: usize,
+    ) -> Option<MarkerCount> {
+        if self.too_large {
+            return None;
+        }
+        let tree = repo.find_commit(commit).ok()?.tree().ok()?;
+        if self.tree == Some(tree.id()) {
+            return Some(MarkerCount {
+                total: self.total,
+                change: 0,
+            });
+        }
+
+        // Nothing to compare against on the first scan
+        let previous = self.tree.map(|_| self.total);
+        match self.tree.and_then(|id| repo.find_tree(id).ok()) {
+            Some(old_tree) => {
+                let diff = repo
+                    .diff_tree_to_tree(Some(&old_tree), Some(&tree), None)
+                    .ok()?;
+                for delta in diff.deltas() {
+                    if let Some(path) = delta.old_file().path() {
+                        self.remove(path);
+                    }
+                    if let Some(path) = delta.new_file().path() {
+                        self.insert(repo, path, delta.new_file().id());
+                    }
+                }
+            }
+            None => {
+                self.files.clear();
+                self.total = 0;
+                let mut blobs = Vec::new();
+                let walked = tree.walk(TreeWalkMode::PreOrder, |dir, entry| {
+                    if entry.kind() == Some(git2::ObjectType::Blob) {
+                        if let Some(name) = entry.name() {
+                            blobs.push((Path::new(dir).join(name), entry.id()));
+                        }
+                    }
+                    if blobs.len() > max_files {
+                        return TreeWalkResult::Abort;
+                    }
+                    TreeWalkResult::Ok
+                });
+                if blobs.len() > max_files {
+                    self.too_large = true;
+                    return None;
+                }
+                walked.ok()?;
+                for (path, blob) in blobs {
+                    self.insert(repo, &path, blob);
+                }
+            }
+        }
+        self.tree = Some(tree.id());
+
+        Some(MarkerCount {
+            total: self.total,
+            change: previous.map_or(0, |previous| self.total as i32 - previous as i32),
+        })
+    }
+
+    /// Count a blob's markers and record them under its path
+    fn insert(&mut self, repo: &Repository, path: &Path, blob: Oid) {
+        if blob.is_zero() || repo.is_path_ignored(path).unwrap_or(false) {
+            return;
+        }
+        let Ok(blob) = repo.find_blob(blob) else {
+            return;
+        };
+        if blob.is_binary() || blob.size() > MAX_BLOB_SIZE {
+            return;
+        }
+        let count = count_markers(blob.content());
+        if count > 0 {
+            self.total += count;
+            self.files.insert(path.to_path_buf(), count);
+        }
+    }
+
+    /// Forget the markers recorded for a path
+    fn remove(&mut self, path: &Path) {
+        if let Some(count) = self.files.remove(path) {
+            self.total -= count;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::fixtures::{commit_files, init_repo};
+    use std::fs;
+
+    #[test]
+    fn test_count_markers_whole_words() {
+        assert_eq!(count_markers(b"// TODO: fix\n# FIXME later\n/* HACK */"), 3);
+        assert_eq!(count_markers(b"TODOS MY_TODO todo XHACK"), 0);
+        assert_eq!(count_markers(b"TODO(me) TODO-2 TODO"), 3);
+    }
+
+    #[test]
+    fn test_incremental_scan_matches_full_scan() {
+        let (dir, repo) = init_repo();
+        fs::write(dir.path().join(".gitignore"), "generated.rs\n").unwrap();
+
+        let first = commit_files(
+            &repo,
+            &[
+                ("a.rs", "// TODO one\n// TODO two\n"),
+                ("b.rs", "// FIXME\n"),
+                ("clean.rs", "fn main() {}\n"),
+            ],
+        );
+        let mut index = MarkerIndex::default();
+        let count = index.advance(&repo, first).unwrap();
+        assert_eq!(count.total, 3);
+
+        // Force-add an ignored file: it shouldn't be counted
+        let mut git_index = repo.index().unwrap();
+        fs::write(dir.path().join("generated.rs"), "// TODO TODO TODO\n").unwrap();
+        git_index.add_path(Path::new("generated.rs")).unwrap();
+        git_index.write().unwrap();
+        let second = commit_files(&repo, &[("a.rs", "// TODO one\n"), ("c.rs", "// HACK\n")]);
+
+        let count = index.advance(&repo, second).unwrap();
+        assert_eq!(
+            count,
+            MarkerCount {
+                total: 3,
+                change: 0
+            }
+        );
+
+        let third = commit_files(&repo, &[("b.rs", "fn fixed() {}\n")]);
+        let count = index.advance(&repo, third).unwrap();
+        assert_eq!(
+            count,
+            MarkerCount {
+                total: 2,
+                change: -1
+            }
+        );
+
+        // A fresh full scan agrees with the incrementally updated index
+        let mut full = MarkerIndex::default();
+        assert_eq!(full.advance(&repo, third).unwrap().total, 2);
+        assert_eq!(full.files, index.files);
+    }
+
+    #[test]
+    fn test_large_trees_are_not_counted() {
+        let (_dir, repo) = init_repo();
+        let first = commit_files(&repo, &[("a.rs", "// TODO\n"), ("b.rs", "// TODO\n")]);
+        let mut index = MarkerIndex::default();
+        assert!(index.advance_within(&repo, first, 2).is_some());
+
+        let second = commit_files(&repo, &[("c.rs", "// TODO\n")]);
+        let mut index = MarkerIndex::default();
+        assert_eq!(index.advance_within(&repo, second, 2), None);
+        // Giving up once is enough, even when the tree shrinks again
+        assert_eq!(index.advance_within(&repo, first, 2), None);
+    }
+}
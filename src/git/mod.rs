@@ -1,5 +1,17 @@
+mod conventional;
+mod discovery;
+#[cfg(test)]
+pub(crate) mod fixtures;
+mod hooks;
+mod languages;
+mod markers;
 mod stats;
 
+pub use conventional::{parse_conventional, ConventionalCommit};
+pub use discovery::{default_ignore, DiscoveryOptions};
+pub use hooks::{hook_commit_hashes, install_hook, uninstall_hook, GitHook, HookAction};
+pub use markers::{MarkerCount, MarkerIndex};
 pub use stats::{
-    format_time_ago, get_repo_commits, CommitInfo, DetectedCommit, GitStats, GitTracker,
+    format_time_ago, get_repo_commits, CommitInfo, CommitSize, DetectedCommit, DetectedPush,
+    DetectedTag, DiffStats, GitStats, GitTracker, RepoInfo, WatchMode, WorkingTreeStatus,
 };
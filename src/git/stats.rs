@@ -1,7 +1,12 @@
+use super::conventional::{parse_conventional, ConventionalCommit};
+use super::discovery::{common_dir, discover_repos, DiscoveryOptions, RepoSource};
+use super::languages::language_for_file;
+use super::markers::{MarkerCount, MarkerIndex};
 use chrono::{DateTime, Local, TimeZone};
 use git2::Repository;
-use std::collections::HashMap;
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
 /// Information about a commit from git log (for the commit picker)
 #[derive(Debug, Clone)]
@@ -14,16 +19,20 @@ pub struct CommitInfo {
     pub message: String,
     /// Commit timestamp
     pub timestamp: DateTime<Local>,
+    /// Author name
+    pub author_name: String,
+    /// Author email
+    pub author_email: String,
     /// Project identifier (remote URL or absolute path)
     pub project_id: String,
     /// Project display name (folder name)
     pub project_name: String,
 }
 
-/// Get the list of commits from the current repository
+/// Get the list of commits from the repository containing `path`
 /// Returns up to `limit` commits from the git log
-pub fn get_repo_commits(limit: usize) -> Vec<CommitInfo> {
-    let repo = match Repository::discover(".") {
+pub fn get_repo_commits(path: &Path, limit: usize) -> Vec<CommitInfo> {
+    let repo = match Repository::discover(path) {
         Ok(r) => r,
         Err(_) => return Vec::new(),
     };
@@ -83,16 +92,16 @@ pub fn get_repo_commits(limit: usize) -> Vec<CommitInfo> {
             .unwrap_or("")
             .to_string();
 
-        let timestamp = Local
-            .timestamp_opt(commit.time().seconds(), 0)
-            .single()
-            .unwrap_or_else(Local::now);
+        let timestamp = git_time_to_local(commit.time());
+        let author = commit.author();
 
         commits.push(CommitInfo {
             hash,
             short_hash,
             message,
             timestamp,
+            author_name: author.name().unwrap_or("").to_string(),
+            author_email: author.email().unwrap_or("").to_string(),
             project_id: project_id.clone(),
             project_name: project_name.clone(),
         });
@@ -128,10 +137,357 @@ fn get_project_name_from_repo(repo: &Repository) -> String {
 pub struct DetectedCommit {
     /// Git commit hash
     pub commit_hash: String,
+    /// When the commit was authored
+    pub authored_at: DateTime<Local>,
+    /// When the commit was created (differs from author time after rebase/amend)
+    pub committed_at: DateTime<Local>,
+    /// When Kanitomo noticed the commit
+    pub detected_at: DateTime<Local>,
+    /// Author name
+    pub author_name: String,
+    /// Author email
+    pub author_email: String,
+    /// Whether the author matches one of the user's identities
+    pub is_own: bool,
     /// Project identifier (remote URL or absolute path)
     pub project_id: String,
     /// Project display name (folder name)
     pub project_name: String,
+    /// Lines and files changed (None for merges and teammates' commits)
+    pub diff: Option<DiffStats>,
+    /// Conventional Commits type/scope (None if the subject doesn't follow it)
+    pub conventional: Option<ConventionalCommit>,
+    /// TODO/FIXME/HACK markers in the tree after the commit
+    /// (only known for commits seen moving HEAD, not for imports or hooks)
+    pub markers: Option<MarkerCount>,
+}
+
+/// A tag that appeared in a watched repository
+#[derive(Debug, Clone)]
+pub struct DetectedTag {
+    /// Tag name (e.g. "v1.2.0")
+    pub name: String,
+    /// Hash of the tagged commit
+    pub commit_hash: String,
+    /// Whether this is an annotated tag (lightweight tags have no tagger or message)
+    pub annotated: bool,
+    /// Annotated tag message (first line)
+    pub message: Option<String>,
+    /// Tagger time for annotated tags, detection time for lightweight ones
+    pub tagged_at: DateTime<Local>,
+    /// Project identifier (remote URL or absolute path)
+    pub project_id: String,
+    /// Project display name (folder name)
+    pub project_name: String,
+}
+
+/// Local commits that reached a remote-tracking ref since the last check
+#[derive(Debug, Clone)]
+pub struct DetectedPush {
+    /// Project display name (folder name)
+    pub project_name: String,
+    /// How many of the user's unpushed commits were pushed
+    pub commit_count: usize,
+}
+
+/// Uncommitted changes in a repository's working tree
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkingTreeStatus {
+    /// Files changed in the working tree but not staged
+    pub modified: usize,
+    /// Files with staged changes
+    pub staged: usize,
+    /// Files git doesn't know about (ignored files excluded)
+    pub untracked: usize,
+    /// Modification time of the oldest modified or staged file still on disk.
+    /// Untracked files don't count: a scratch file left lying around isn't work in progress.
+    pub oldest_change: Option<DateTime<Local>>,
+}
+
+impl WorkingTreeStatus {
+    /// Read the status of a repository's working tree (bare repositories are always clean)
+    fn for_repo(repo: &Repository) -> Self {
+        let mut status = Self::default();
+        let Some(workdir) = repo.workdir() else {
+            return status;
+        };
+
+        let mut options = git2::StatusOptions::new();
+        options
+            .include_untracked(true)
+            .recurse_untracked_dirs(true)
+            .exclude_submodules(true); // Submodules are tracked as repositories of their own
+        let Ok(entries) = repo.statuses(Some(&mut options)) else {
+            return status;
+        };
+
+        for entry in entries.iter() {
+            let flags = entry.status();
+            if flags.is_wt_new() {
+                status.untracked += 1;
+                continue;
+            }
+            if flags.intersects(
+                git2::Status::INDEX_NEW
+                    | git2::Status::INDEX_MODIFIED
+                    | git2::Status::INDEX_DELETED
+                    | git2::Status::INDEX_RENAMED
+                    | git2::Status::INDEX_TYPECHANGE,
+            ) {
+                status.staged += 1;
+            }
+            if flags.intersects(
+                git2::Status::WT_MODIFIED
+                    | git2::Status::WT_DELETED
+                    | git2::Status::WT_RENAMED
+                    | git2::Status::WT_TYPECHANGE,
+            ) {
+                status.modified += 1;
+            }
+
+            let modified_at = entry
+                .path()
+                .and_then(|path| std::fs::symlink_metadata(workdir.join(path)).ok())
+                .and_then(|metadata| metadata.modified().ok())
+                .map(DateTime::<Local>::from);
+            if let Some(modified_at) = modified_at {
+                status.oldest_change = Some(match status.oldest_change {
+                    Some(oldest) => oldest.min(modified_at),
+                    None => modified_at,
+                });
+            }
+        }
+
+        status
+    }
+
+    /// Read the working tree status of repositories (by git dir), opening each one afresh
+    /// so this can run away from the tracker, e.g. on a background thread
+    pub fn read_all(git_dirs: &[PathBuf]) -> Vec<(PathBuf, Self)> {
+        git_dirs
+            .iter()
+            .filter_map(|git_dir| {
+                let repo = Repository::open(git_dir).ok()?;
+                Some((git_dir.clone(), Self::for_repo(&repo)))
+            })
+            .collect()
+    }
+
+    /// Number of files with uncommitted changes
+    pub fn changed_files(&self) -> usize {
+        self.modified + self.staged + self.untracked
+    }
+
+    /// Whether there is anything to commit
+    pub fn is_dirty(&self) -> bool {
+        self.changed_files() > 0
+    }
+}
+
+/// Size of the changes introduced by a commit
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffStats {
+    /// Lines added
+    pub insertions: u32,
+    /// Lines removed
+    pub deletions: u32,
+    /// Number of files touched
+    pub files_changed: u32,
+    /// Languages of the touched files, most files first
+    pub languages: Vec<String>,
+}
+
+/// Rough size category of a commit, from typo fix to huge refactor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CommitSize {
+    Tiny,
+    Small,
+    Medium,
+    Large,
+    Huge,
+}
+
+impl DiffStats {
+    /// Compute diff stats for a commit against its parent
+    /// Merge commits return None: their diff isn't work done in the merge itself
+    fn for_commit(repo: &Repository, commit: &git2::Commit) -> Option<Self> {
+        if commit.parent_count() > 1 {
+            return None;
+        }
+        let parent_tree = commit.parent(0).ok().and_then(|p| p.tree().ok());
+        let tree = commit.tree().ok()?;
+        let diff = repo
+            .diff_tree_to_tree(parent_tree.as_ref(), Some(&tree), None)
+            .ok()?;
+        let stats = diff.stats().ok()?;
+
+        // Count touched files per language
+        let mut counts: Vec<(String, u32)> = Vec::new();
+        for delta in diff.deltas() {
+            let path = delta.new_file().path().or_else(|| delta.old_file().path());
+            let Some(language) = path.and_then(|path| language_for_file(repo, path)) else {
+                continue;
+            };
+            match counts.iter_mut().find(|(name, _)| *name == language) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((language, 1)),
+            }
+        }
+        counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        Some(Self {
+            insertions: stats.insertions() as u32,
+            deletions: stats.deletions() as u32,
+            files_changed: stats.files_changed() as u32,
+            languages: counts.into_iter().map(|(name, _)| name).collect(),
+        })
+    }
+
+    /// Total lines touched
+    pub fn lines_changed(&self) -> u32 {
+        self.insertions + self.deletions
+    }
+
+    /// Categorize the commit by how many lines it touched
+    pub fn size(&self) -> CommitSize {
+        match self.lines_changed() {
+            0..=5 => CommitSize::Tiny,
+            6..=50 => CommitSize::Small,
+            51..=300 => CommitSize::Medium,
+            301..=1000 => CommitSize::Large,
+            _ => CommitSize::Huge,
+        }
+    }
+}
+
+/// Decides which commits were authored by the user
+#[derive(Debug, Clone, Default)]
+pub struct AuthorFilter {
+    /// Configured author names or emails (compared case-insensitively)
+    identities: Vec<String>,
+}
+
+impl AuthorFilter {
+    /// Create a filter from configured identities.
+    /// An empty list falls back to each repository's `user.email`.
+    pub fn new(identities: Vec<String>) -> Self {
+        Self { identities }
+    }
+
+    /// Resolve the identities that count as "me" in a given repository
+    fn identities_for(&self, repo: &Repository) -> Vec<String> {
+        if !self.identities.is_empty() {
+            return self.identities.clone();
+        }
+
+        repo.config()
+            .ok()
+            .and_then(|config| config.get_string("user.email").ok())
+            .into_iter()
+            .collect()
+    }
+
+    /// Check an author against resolved identities.
+    /// With no identity to compare against, every commit is accepted.
+    fn matches(identities: &[String], name: &str, email: &str) -> bool {
+        identities.is_empty()
+            || identities
+                .iter()
+                .any(|id| id.eq_ignore_ascii_case(email) || id.eq_ignore_ascii_case(name))
+    }
+}
+
+/// How a repository's HEAD moved between two checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadMove {
+    /// HEAD moved forward to a descendant of the previous commit
+    Advanced,
+    /// History on the same branch was rewritten (rebase, amend)
+    Rewritten,
+    /// HEAD moved back to one of its ancestors (e.g. `git reset`)
+    Rewound,
+    /// HEAD now points at a different branch, not ahead of the previous commit
+    SwitchedBranch,
+}
+
+impl HeadMove {
+    /// Whether this move brings in new commits that should feed Kani
+    /// Rewrites report every commit under its new hash; the state skips the ones it
+    /// already tracks by author and author time.
+    pub fn is_feeding(&self) -> bool {
+        matches!(self, HeadMove::Advanced | HeadMove::Rewritten)
+    }
+}
+
+/// A HEAD movement detected in one repository
+#[derive(Debug, Clone)]
+pub struct HeadChange {
+    /// How HEAD moved
+    pub kind: HeadMove,
+    /// Commits reachable from the new HEAD but not from the old one (oldest first).
+    /// Always empty for rewinds and branch switches.
+    pub commits: Vec<DetectedCommit>,
+}
+
+/// Repositories that appeared or disappeared since the last rescan
+#[derive(Debug, Clone, Default)]
+pub struct RepoListChange {
+    /// Newly discovered repositories
+    pub added: Vec<RepoInfo>,
+    /// Repositories that no longer exist
+    pub removed: Vec<RepoInfo>,
+}
+
+impl RepoListChange {
+    /// Whether the set of tracked repositories stayed the same
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+/// A path the file watcher should register
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchPath {
+    /// File or directory to watch
+    pub path: PathBuf,
+    /// Whether to watch subdirectories too
+    pub recursive: bool,
+}
+
+/// Check whether a path relative to a git dir can move HEAD.
+/// Lock files are skipped: the rename that completes a ref transaction
+/// shows up as a change to the ref itself.
+fn is_ref_path(relative: &Path, is_worktree_dir: bool) -> bool {
+    if relative.extension().is_some_and(|ext| ext == "lock") {
+        return false;
+    }
+    let head_files =
+        is_worktree_dir && (relative == Path::new("HEAD") || relative == Path::new("logs/HEAD"));
+    head_files || relative == Path::new("packed-refs") || relative.starts_with("refs")
+}
+
+/// Snapshot of where HEAD pointed at the last check
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct HeadState {
+    /// Commit hash HEAD resolved to
+    oid: String,
+    /// Branch name, or None when HEAD is detached
+    branch: Option<String>,
+}
+
+/// Verify checks in a row that must catch changes the watcher missed before a
+/// repository is polled instead (a single late event isn't a silent watcher)
+const SILENT_WATCHER_MISSES: u32 = 3;
+
+/// Maximum number of commits reported for a single HEAD movement
+const MAX_COMMITS_PER_MOVE: usize = 200;
+
+/// Convert a git timestamp to local time
+fn git_time_to_local(time: git2::Time) -> DateTime<Local> {
+    Local
+        .timestamp_opt(time.seconds(), 0)
+        .single()
+        .unwrap_or_else(Local::now)
 }
 
 /// Statistics about git activity (display purposes)
@@ -141,8 +497,78 @@ pub struct GitStats {
     pub in_git_repo: bool,
     /// Number of repositories being tracked
     pub repo_count: usize,
-    /// Names of all tracked repositories
-    pub repo_names: Vec<String>,
+    /// All tracked repositories
+    pub repos: Vec<RepoInfo>,
+}
+
+impl GitStats {
+    /// Total number of unpushed commits across all repositories
+    pub fn unpushed_total(&self) -> usize {
+        self.repos.iter().map(|repo| repo.unpushed).sum()
+    }
+
+    /// The repository whose unpushed work has been waiting the longest
+    pub fn oldest_unpushed(&self) -> Option<&RepoInfo> {
+        self.repos
+            .iter()
+            .filter(|repo| repo.oldest_unpushed.is_some())
+            .min_by_key(|repo| repo.oldest_unpushed)
+    }
+
+    /// Uncommitted changes summed over all repositories, with the oldest change overall
+    pub fn working_tree_total(&self) -> WorkingTreeStatus {
+        let mut total = WorkingTreeStatus::default();
+        for status in self.repos.iter().map(|repo| &repo.status) {
+            total.modified += status.modified;
+            total.staged += status.staged;
+            total.untracked += status.untracked;
+            total.oldest_change = match (total.oldest_change, status.oldest_change) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+        }
+        total
+    }
+}
+
+/// Display information about one tracked repository
+#[derive(Debug, Clone)]
+pub struct RepoInfo {
+    /// Repository display name (folder name)
+    pub name: String,
+    /// Project identifier (remote URL or absolute path), as stored in the history
+    pub project_id: String,
+    /// Working directory (the git dir for bare repositories)
+    pub path: PathBuf,
+    /// Where the repository was discovered
+    pub source: RepoSource,
+    /// How the repository is being watched for commits
+    pub watch_mode: WatchMode,
+    /// The user's commits on HEAD that no remote-tracking ref contains yet
+    pub unpushed: usize,
+    /// Commit time of the oldest unpushed commit
+    pub oldest_unpushed: Option<DateTime<Local>>,
+    /// Uncommitted changes in the working tree (as of the last status refresh)
+    pub status: WorkingTreeStatus,
+}
+
+/// How a repository is watched for HEAD changes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WatchMode {
+    /// Filesystem events from the OS watcher
+    #[default]
+    Events,
+    /// Periodic HEAD checks (the watcher failed or stayed silent)
+    Polling,
+}
+
+impl std::fmt::Display for WatchMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WatchMode::Events => write!(f, "events"),
+            WatchMode::Polling => write!(f, "polling"),
+        }
+    }
 }
 
 /// Format a datetime as a human-readable "X ago" string
@@ -173,112 +599,701 @@ pub fn format_time_ago(time: Option<DateTime<Local>>) -> String {
 pub struct GitTracker {
     /// All tracked repositories
     repos: Vec<Repository>,
-    /// Last known HEAD commit hash per repository (keyed by repo path)
-    last_heads: HashMap<PathBuf, String>,
+    /// Where each repository was discovered (keyed by repo path)
+    sources: HashMap<PathBuf, RepoSource>,
+    /// Last known HEAD per repository (keyed by repo path)
+    last_heads: HashMap<PathBuf, HeadState>,
+    /// How each repository is watched (keyed by repo path, missing means events)
+    watch_modes: HashMap<PathBuf, WatchMode>,
+    /// Verify checks in a row that found changes the watcher didn't report (keyed by repo path)
+    watch_misses: HashMap<PathBuf, u32>,
+    /// Known tags and their targets (keyed by common git dir, shared by worktrees)
+    known_tags: HashMap<PathBuf, HashMap<String, String>>,
+    /// The user's unpushed commits on HEAD (keyed by repo path, missing means no remote)
+    unpushed: HashMap<PathBuf, Vec<git2::Oid>>,
+    /// Working tree status per repository (keyed by repo path)
+    statuses: HashMap<PathBuf, WorkingTreeStatus>,
+    /// TODO/FIXME/HACK counts per repository (keyed by repo path, built away from the
+    /// tracker with [`MarkerIndex::build_all`]; trees too large to scan are never counted)
+    markers: HashMap<PathBuf, MarkerIndex>,
+    /// Which commit authors count as the user
+    author_filter: AuthorFilter,
+    /// Options used for discovery (kept for rescans)
+    options: DiscoveryOptions,
+    /// Directories read during the last discovery pass
+    scanned_dirs: Vec<PathBuf>,
 }
 
 impl GitTracker {
     /// Create a new git tracker for the current directory
     /// If in a git repo, tracks just that repo
-    /// If not, scans immediate subdirectories for git repos
+    /// If not, scans subdirectories for git repos
     pub fn new() -> Self {
-        let repos = Self::discover_repos();
-        let mut last_heads = HashMap::new();
-
-        for repo in &repos {
-            if let Some(head) = repo
-                .head()
-                .ok()
-                .and_then(|r| r.target())
-                .map(|oid| oid.to_string())
-            {
-                last_heads.insert(repo.path().to_path_buf(), head);
+        Self::discover(&DiscoveryOptions::default(), Vec::new())
+    }
+
+    /// Create a git tracker for the repositories matching `options`,
+    /// only crediting commits by the given authors
+    pub fn discover(options: &DiscoveryOptions, authors: Vec<String>) -> Self {
+        let mut tracker = Self {
+            repos: Vec::new(),
+            sources: HashMap::new(),
+            last_heads: HashMap::new(),
+            watch_modes: HashMap::new(),
+            watch_misses: HashMap::new(),
+            known_tags: HashMap::new(),
+            unpushed: HashMap::new(),
+            statuses: HashMap::new(),
+            markers: HashMap::new(),
+            author_filter: AuthorFilter::new(authors),
+            options: options.clone(),
+            scanned_dirs: Vec::new(),
+        };
+        tracker.rescan();
+        tracker
+    }
+
+    /// Run discovery again, picking up new repositories and dropping removed ones
+    pub fn rescan(&mut self) -> RepoListChange {
+        let discovery = discover_repos(&self.options);
+        self.scanned_dirs = discovery.scanned_dirs;
+
+        let mut change = RepoListChange::default();
+        let found: HashSet<PathBuf> = discovery
+            .repos
+            .iter()
+            .map(|(repo, _)| repo.path().to_path_buf())
+            .collect();
+
+        for repo in self.repos.iter().filter(|r| !found.contains(r.path())) {
+            change.removed.push(self.repo_info(repo));
+            self.sources.remove(repo.path());
+            self.last_heads.remove(repo.path());
+            self.watch_modes.remove(repo.path());
+            self.watch_misses.remove(repo.path());
+            self.unpushed.remove(repo.path());
+            self.statuses.remove(repo.path());
+            self.markers.remove(repo.path());
+        }
+        let mut repos: Vec<Repository> = std::mem::take(&mut self.repos)
+            .into_iter()
+            .filter(|r| found.contains(r.path()))
+            .collect();
+
+        for (repo, source) in discovery.repos {
+            if repos.iter().any(|r| r.path() == repo.path()) {
+                continue;
+            }
+            // Start from the current HEAD and tags so existing history isn't counted as new
+            if let Some(head) = Self::head_state(&repo) {
+                self.last_heads.insert(repo.path().to_path_buf(), head);
             }
+            self.known_tags
+                .entry(common_dir(&repo))
+                .or_insert_with(|| Self::tag_targets(&repo));
+            if let Some(unpushed) = Self::unpushed_commits(&repo, &self.author_filter) {
+                self.unpushed.insert(repo.path().to_path_buf(), unpushed);
+            }
+            self.statuses.insert(
+                repo.path().to_path_buf(),
+                WorkingTreeStatus::for_repo(&repo),
+            );
+            self.sources.insert(repo.path().to_path_buf(), source);
+            repos.push(repo);
+            change.added.push(self.repo_info(repos.last().unwrap()));
         }
 
-        Self { repos, last_heads }
+        repos.sort_by_key(Self::get_project_name);
+        self.repos = repos;
+        change
+    }
+
+    /// Whether a tracked repository's git directory has disappeared
+    pub fn has_missing_repos(&self) -> bool {
+        self.repos.iter().any(|repo| !repo.path().exists())
     }
 
-    /// Discover git repositories
-    /// First checks if current directory is a git repo, if so returns just that
-    /// Otherwise scans immediate subdirectories for git repos
-    fn discover_repos() -> Vec<Repository> {
-        // First, check if we're in a git repo
-        if let Ok(repo) = Repository::discover(".") {
-            return vec![repo];
+    /// Check whether a path lies inside one of the tracked git directories
+    /// (including the main git dir shared by worktrees)
+    pub fn is_in_git_dir(&self, path: &Path) -> bool {
+        self.repos
+            .iter()
+            .any(|repo| path.starts_with(repo.path()) || path.starts_with(common_dir(repo)))
+    }
+
+    /// Directories to watch for repositories being added or removed
+    pub fn scanned_dirs(&self) -> &[PathBuf] {
+        &self.scanned_dirs
+    }
+
+    /// Get display information for all tracked repositories
+    pub fn repo_infos(&self) -> Vec<RepoInfo> {
+        self.repos.iter().map(|repo| self.repo_info(repo)).collect()
+    }
+
+    /// Get display information for one repository
+    fn repo_info(&self, repo: &Repository) -> RepoInfo {
+        let unpushed = self.unpushed.get(repo.path());
+        RepoInfo {
+            name: Self::get_project_name(repo),
+            project_id: Self::get_project_id(repo),
+            path: repo.workdir().unwrap_or(repo.path()).to_path_buf(),
+            source: self
+                .sources
+                .get(repo.path())
+                .cloned()
+                .unwrap_or(RepoSource::CurrentDir),
+            watch_mode: self.watch_mode(repo.path()),
+            unpushed: unpushed.map_or(0, |commits| commits.len()),
+            oldest_unpushed: unpushed
+                .into_iter()
+                .flatten()
+                .filter_map(|oid| repo.find_commit(*oid).ok())
+                .map(|commit| git_time_to_local(commit.time()))
+                .min(),
+            status: self.statuses.get(repo.path()).cloned().unwrap_or_default(),
+        }
+    }
+
+    /// Re-read the working tree status of the given repositories (by git dir)
+    pub fn refresh_statuses(&mut self, git_dirs: &[PathBuf]) {
+        for repo in self
+            .repos
+            .iter()
+            .filter(|r| git_dirs.iter().any(|d| d == r.path()))
+        {
+            self.statuses
+                .insert(repo.path().to_path_buf(), WorkingTreeStatus::for_repo(repo));
+        }
+    }
+
+    /// Store working tree statuses read with [`WorkingTreeStatus::read_all`], skipping
+    /// repositories that stopped being tracked in the meantime
+    pub fn set_statuses(&mut self, statuses: Vec<(PathBuf, WorkingTreeStatus)>) {
+        for (git_dir, status) in statuses {
+            if self.repos.iter().any(|r| r.path() == git_dir) {
+                self.statuses.insert(git_dir, status);
+            }
         }
+    }
 
-        // Not in a git repo, scan immediate subdirectories
-        let mut repos = Vec::new();
+    /// Git dirs of the repositories without a marker index yet
+    pub fn unindexed_repos(&self) -> Vec<PathBuf> {
+        self.repos
+            .iter()
+            .filter(|repo| !self.markers.contains_key(repo.path()))
+            .map(|repo| repo.path().to_path_buf())
+            .collect()
+    }
+
+    /// Store marker indexes built with [`MarkerIndex::build_all`], skipping repositories
+    /// that stopped being tracked or got an index in the meantime
+    pub fn set_marker_indexes(&mut self, indexes: Vec<(PathBuf, MarkerIndex)>) {
+        for (git_dir, index) in indexes {
+            if self.repos.iter().any(|r| r.path() == git_dir) {
+                self.markers.entry(git_dir).or_insert(index);
+            }
+        }
+    }
 
-        if let Ok(entries) = std::fs::read_dir(".") {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if path.is_dir() {
-                    // Try to open as a git repository
-                    if let Ok(repo) = Repository::open(&path) {
-                        repos.push(repo);
+    /// How a repository (by git dir) is being watched
+    pub fn watch_mode(&self, git_dir: &Path) -> WatchMode {
+        self.watch_modes.get(git_dir).copied().unwrap_or_default()
+    }
+
+    /// Change how a repository is watched; returns whether the mode changed
+    pub fn set_watch_mode(&mut self, git_dir: &Path, mode: WatchMode) -> bool {
+        let previous = self.watch_modes.insert(git_dir.to_path_buf(), mode);
+        previous.unwrap_or_default() != mode
+    }
+
+    /// Record whether a verify check of a watched repository (by git dir) found
+    /// changes its watcher didn't report. After `SILENT_WATCHER_MISSES` misses in a
+    /// row the repository is polled instead; returns whether that happened.
+    pub fn record_watch_check(&mut self, git_dir: &Path, missed: bool) -> bool {
+        if !missed {
+            self.watch_misses.remove(git_dir);
+            return false;
+        }
+
+        let misses = self.watch_misses.entry(git_dir.to_path_buf()).or_insert(0);
+        *misses += 1;
+        if *misses < SILENT_WATCHER_MISSES {
+            return false;
+        }
+        self.watch_misses.remove(git_dir);
+        self.set_watch_mode(git_dir, WatchMode::Polling)
+    }
+
+    /// Get the git dirs of all repositories watched with the given mode
+    pub fn repos_watched_by(&self, mode: WatchMode) -> Vec<PathBuf> {
+        self.repos
+            .iter()
+            .filter(|repo| self.watch_mode(repo.path()) == mode)
+            .map(|repo| repo.path().to_path_buf())
+            .collect()
+    }
+
+    /// Check the given repositories (by git dir) for HEAD movements since the last check
+    /// Returns one entry per repository whose HEAD moved
+    pub fn check_repos(&mut self, git_dirs: &[PathBuf]) -> Vec<HeadChange> {
+        let mut changes = Vec::new();
+        // Worktrees share history, so the same commit can show up from several HEADs
+        let mut seen = HashSet::new();
+
+        for repo in self
+            .repos
+            .iter()
+            .filter(|r| git_dirs.iter().any(|d| d == r.path()))
+        {
+            let repo_path = repo.path().to_path_buf();
+            let Some(current) = Self::head_state(repo) else {
+                continue;
+            };
+
+            // Don't count the initial HEAD as a new commit
+            let Some(old) = self.last_heads.insert(repo_path.clone(), current.clone()) else {
+                continue;
+            };
+            if old.oid == current.oid {
+                continue;
+            }
+
+            let kind = Self::classify_head_move(repo, &old, &current);
+            let mut commits: Vec<DetectedCommit> = if kind.is_feeding() {
+                Self::commits_between(repo, &self.author_filter, &old.oid, &current.oid)
+                    .into_iter()
+                    .filter(|commit| seen.insert(commit.commit_hash.clone()))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+
+            // Walk the marker index through each new commit, then to the new HEAD
+            // (until the index is built, commits go without marker counts)
+            if let Some(markers) = self.markers.get_mut(&repo_path) {
+                for commit in &mut commits {
+                    if let Ok(oid) = git2::Oid::from_str(&commit.commit_hash) {
+                        commit.markers = markers.advance(repo, oid);
+                    }
+                }
+                if let Ok(oid) = git2::Oid::from_str(&current.oid) {
+                    markers.advance(repo, oid);
+                }
+            }
+
+            changes.push(HeadChange { kind, commits });
+        }
+
+        changes
+    }
+
+    /// Check the given repositories (by git dir) for tags created since the last check
+    /// Only the user's own tags are reported: annotated tags by their tagger, lightweight
+    /// tags (which have none) by the tagged commit's author. A lightweight tag on a
+    /// commit no remote has can't have come from a fetch, so it counts too.
+    pub fn check_tags(&mut self, git_dirs: &[PathBuf]) -> Vec<DetectedTag> {
+        let mut tags = Vec::new();
+
+        for repo in self
+            .repos
+            .iter()
+            .filter(|r| git_dirs.iter().any(|d| d == r.path()))
+        {
+            let current = Self::tag_targets(repo);
+            // Worktrees share tags; the first one checked updates the snapshot for all
+            let Some(known) = self.known_tags.insert(common_dir(repo), current.clone()) else {
+                continue;
+            };
+
+            let identities = self.author_filter.identities_for(repo);
+            let project_id = Self::get_project_id(repo);
+            let project_name = Self::get_project_name(repo);
+            let remote_targets = Self::remote_targets(repo);
+
+            for (name, target) in &current {
+                if known.get(name) == Some(target) {
+                    continue;
+                }
+                let Ok(oid) = git2::Oid::from_str(target) else {
+                    continue;
+                };
+                let Ok(object) = repo.find_object(oid, None) else {
+                    continue;
+                };
+                let Ok(commit) = object.peel_to_commit() else {
+                    continue; // Tags on trees or blobs aren't releases
+                };
+
+                let annotated = object.as_tag();
+                let is_own = match annotated.and_then(|tag| tag.tagger()) {
+                    Some(tagger) => AuthorFilter::matches(
+                        &identities,
+                        tagger.name().unwrap_or(""),
+                        tagger.email().unwrap_or(""),
+                    ),
+                    None => {
+                        let author = commit.author();
+                        AuthorFilter::matches(
+                            &identities,
+                            author.name().unwrap_or(""),
+                            author.email().unwrap_or(""),
+                        ) || !Self::is_on_remote(repo, &remote_targets, commit.id())
                     }
+                };
+                if !is_own {
+                    continue;
                 }
+
+                tags.push(DetectedTag {
+                    name: name.clone(),
+                    commit_hash: commit.id().to_string(),
+                    annotated: annotated.is_some(),
+                    message: annotated
+                        .and_then(|tag| tag.message())
+                        .and_then(|message| message.lines().next())
+                        .map(|line| line.trim().to_string())
+                        .filter(|line| !line.is_empty()),
+                    tagged_at: annotated
+                        .and_then(|tag| tag.tagger())
+                        .map(|tagger| git_time_to_local(tagger.when()))
+                        .unwrap_or_else(Local::now),
+                    project_id: project_id.clone(),
+                    project_name: project_name.clone(),
+                });
             }
         }
 
-        // Sort by repo name for consistent ordering
-        repos.sort_by(|a, b| {
-            let name_a = a.workdir().and_then(|p| p.file_name());
-            let name_b = b.workdir().and_then(|p| p.file_name());
-            name_a.cmp(&name_b)
-        });
+        tags
+    }
 
-        repos
+    /// Read every tag name and the object it points to
+    fn tag_targets(repo: &Repository) -> HashMap<String, String> {
+        let mut targets = HashMap::new();
+        let Ok(references) = repo.references_glob("refs/tags/*") else {
+            return targets;
+        };
+        for reference in references.filter_map(|r| r.ok()) {
+            if let (Some(name), Some(target)) = (reference.shorthand(), reference.target()) {
+                targets.insert(name.to_string(), target.to_string());
+            }
+        }
+        targets
     }
 
-    /// Get the names of all tracked repositories
-    pub fn repo_names(&self) -> Vec<String> {
+    /// Check the given repositories (by git dir) for unpushed commits that reached a
+    /// remote-tracking ref since the last check, refreshing the unpushed counts
+    pub fn check_pushes(&mut self, git_dirs: &[PathBuf]) -> Vec<DetectedPush> {
+        let mut pushes = Vec::new();
+        // Worktrees can share unpushed commits; count each push once
+        let mut seen = HashSet::new();
+
+        for repo in self
+            .repos
+            .iter()
+            .filter(|r| git_dirs.iter().any(|d| d == r.path()))
+        {
+            let repo_path = repo.path().to_path_buf();
+            let Some(current) = Self::unpushed_commits(repo, &self.author_filter) else {
+                self.unpushed.remove(&repo_path);
+                continue;
+            };
+            let Some(previous) = self.unpushed.insert(repo_path, current.clone()) else {
+                continue;
+            };
+
+            // Commits that left the list because of a reset or rebase weren't pushed
+            let remote_targets = Self::remote_targets(repo);
+            let pushed = previous
+                .iter()
+                .filter(|oid| !current.contains(oid))
+                .filter(|oid| Self::is_on_remote(repo, &remote_targets, **oid))
+                .filter(|oid| seen.insert(**oid))
+                .count();
+
+            if pushed > 0 {
+                pushes.push(DetectedPush {
+                    project_name: Self::get_project_name(repo),
+                    commit_count: pushed,
+                });
+            }
+        }
+
+        pushes
+    }
+
+    /// Find the user's commits reachable from HEAD but from no remote-tracking ref
+    /// Returns None for repositories without remotes (there's nowhere to push) and for
+    /// remotes that were never fetched or pushed, where every commit would be unpushed
+    fn unpushed_commits(repo: &Repository, author_filter: &AuthorFilter) -> Option<Vec<git2::Oid>> {
+        if repo.remotes().ok()?.is_empty() {
+            return None;
+        }
+        let remote_targets = Self::remote_targets(repo);
+        if remote_targets.is_empty() {
+            return None;
+        }
+
+        let mut revwalk = repo.revwalk().ok()?;
+        revwalk.push_head().ok()?;
+        for target in remote_targets {
+            revwalk.hide(target).ok();
+        }
+
+        let identities = author_filter.identities_for(repo);
+        let commits = revwalk
+            .filter_map(|oid| oid.ok())
+            .filter(|oid| {
+                repo.find_commit(*oid).is_ok_and(|commit| {
+                    let author = commit.author();
+                    AuthorFilter::matches(
+                        &identities,
+                        author.name().unwrap_or(""),
+                        author.email().unwrap_or(""),
+                    )
+                })
+            })
+            .collect();
+        Some(commits)
+    }
+
+    /// Read the commits every remote-tracking branch points at
+    fn remote_targets(repo: &Repository) -> Vec<git2::Oid> {
+        let Ok(references) = repo.references_glob("refs/remotes/*") else {
+            return Vec::new();
+        };
+        references
+            .filter_map(|r| r.ok())
+            .filter_map(|reference| reference.target())
+            .collect()
+    }
+
+    /// Check whether a commit is reachable from one of the remote-tracking refs
+    fn is_on_remote(repo: &Repository, remote_targets: &[git2::Oid], oid: git2::Oid) -> bool {
+        remote_targets
+            .iter()
+            .any(|target| *target == oid || repo.graph_descendant_of(*target, oid).unwrap_or(false))
+    }
+
+    /// Find the repositories (by git dir) whose HEAD may have moved after a change to `path`
+    /// Changes to shared refs affect every worktree of the repository
+    pub fn repos_affected_by(&self, path: &Path) -> Vec<PathBuf> {
         self.repos
             .iter()
-            .filter_map(|r| {
-                r.workdir()
-                    .and_then(|p| p.file_name())
-                    .and_then(|n| n.to_str())
-                    .map(|s| s.to_string())
+            .filter(|repo| {
+                let in_git_dir = path
+                    .strip_prefix(repo.path())
+                    .is_ok_and(|rel| is_ref_path(rel, true));
+                let in_common_dir = path
+                    .strip_prefix(common_dir(repo))
+                    .is_ok_and(|rel| is_ref_path(rel, false));
+                in_git_dir || in_common_dir
             })
+            .map(|repo| repo.path().to_path_buf())
             .collect()
     }
 
-    /// Check if there's a new commit since last check in any repository
-    /// Returns details about the detected commit if found
-    pub fn check_for_new_commit(&mut self) -> Option<DetectedCommit> {
+    /// Read the current HEAD commit and branch of a repository
+    fn head_state(repo: &Repository) -> Option<HeadState> {
+        let head = repo.head().ok()?;
+        let oid = head.target()?.to_string();
+        let branch = if head.is_branch() {
+            head.shorthand().map(|s| s.to_string())
+        } else {
+            None
+        };
+        Some(HeadState { oid, branch })
+    }
+
+    /// Work out how HEAD moved from `old` to `new`
+    fn classify_head_move(repo: &Repository, old: &HeadState, new: &HeadState) -> HeadMove {
+        let switched = old.branch != new.branch;
+        let (Ok(old_oid), Ok(new_oid)) =
+            (git2::Oid::from_str(&old.oid), git2::Oid::from_str(&new.oid))
+        else {
+            return if switched {
+                HeadMove::SwitchedBranch
+            } else {
+                HeadMove::Rewritten
+            };
+        };
+
+        // Ancestry comes first: `git checkout -b topic && git commit` (or the detached
+        // HEAD of a rebase) can move HEAD to another branch and ahead within one check
+        if repo.graph_descendant_of(new_oid, old_oid).unwrap_or(false) {
+            HeadMove::Advanced
+        } else if switched {
+            HeadMove::SwitchedBranch
+        } else if repo.graph_descendant_of(old_oid, new_oid).unwrap_or(false) {
+            HeadMove::Rewound
+        } else {
+            HeadMove::Rewritten
+        }
+    }
+
+    /// Collect the commits reachable from `new` but not from `old`, oldest first
+    fn commits_between(
+        repo: &Repository,
+        author_filter: &AuthorFilter,
+        old: &str,
+        new: &str,
+    ) -> Vec<DetectedCommit> {
+        let mut commits = Vec::new();
+
+        let Ok(new_oid) = git2::Oid::from_str(new) else {
+            return commits;
+        };
+        let mut revwalk = match repo.revwalk() {
+            Ok(r) => r,
+            Err(_) => return commits,
+        };
+        if revwalk.push(new_oid).is_err() {
+            return commits;
+        }
+        // The old commit may be gone after a rewrite followed by gc; walk anyway
+        if let Ok(old_oid) = git2::Oid::from_str(old) {
+            revwalk.hide(old_oid).ok();
+        }
+        revwalk
+            .set_sorting(git2::Sort::TOPOLOGICAL | git2::Sort::REVERSE)
+            .ok();
+
+        // Keep the most recent commits if a huge range came in at once
+        let mut oids: Vec<git2::Oid> = revwalk.filter_map(|o| o.ok()).collect();
+        if oids.len() > MAX_COMMITS_PER_MOVE {
+            oids.drain(..oids.len() - MAX_COMMITS_PER_MOVE);
+        }
+
+        let project_id = Self::get_project_id(repo);
+        let project_name = Self::get_project_name(repo);
+        let identities = author_filter.identities_for(repo);
+
+        for oid in oids {
+            if let Ok(commit) = repo.find_commit(oid) {
+                commits.push(Self::detect_commit(
+                    repo,
+                    &commit,
+                    &identities,
+                    &project_id,
+                    &project_name,
+                ));
+            }
+        }
+
+        commits
+    }
+
+    /// Collect the user's own commits authored since `since` in every repository,
+    /// leaving out the hashes in `known` (commits already recorded)
+    /// Walks HEAD and all local branches, newest first
+    pub fn own_commits_since(
+        &self,
+        since: DateTime<Local>,
+        known: &HashSet<&str>,
+    ) -> Vec<DetectedCommit> {
+        let mut commits = Vec::new();
+
         for repo in &self.repos {
-            let repo_path = repo.path().to_path_buf();
-            let current_head = repo
-                .head()
-                .ok()
-                .and_then(|r| r.target())
-                .map(|oid| oid.to_string());
-
-            if let Some(current) = current_head {
-                let is_new = match self.last_heads.get(&repo_path) {
-                    Some(old) => old != &current,
-                    None => false, // Don't count initial HEAD as new commit
-                };
+            let Ok(mut revwalk) = repo.revwalk() else {
+                continue;
+            };
+            revwalk.push_head().ok();
+            revwalk.push_glob("heads").ok();
+            revwalk.set_sorting(git2::Sort::TIME).ok();
 
-                if is_new {
-                    self.last_heads.insert(repo_path, current.clone());
+            let project_id = Self::get_project_id(repo);
+            let project_name = Self::get_project_name(repo);
+            let identities = self.author_filter.identities_for(repo);
 
-                    let project_id = Self::get_project_id(repo);
-                    let project_name = Self::get_project_name(repo);
+            for oid in revwalk.filter_map(|o| o.ok()) {
+                let Ok(commit) = repo.find_commit(oid) else {
+                    continue;
+                };
+                // Walk is sorted by committer time, so everything after this is older
+                if git_time_to_local(commit.time()) < since {
+                    break;
+                }
 
-                    return Some(DetectedCommit {
-                        commit_hash: current,
-                        project_id,
-                        project_name,
-                    });
+                // Skip teammates' and known commits before paying for their diff
+                let author = commit.author();
+                let is_own = AuthorFilter::matches(
+                    &identities,
+                    author.name().unwrap_or(""),
+                    author.email().unwrap_or(""),
+                );
+                if !is_own
+                    || git_time_to_local(author.when()) < since
+                    || known.contains(oid.to_string().as_str())
+                {
+                    continue;
                 }
+
+                commits.push(Self::detect_commit(
+                    repo,
+                    &commit,
+                    &identities,
+                    &project_id,
+                    &project_name,
+                ));
             }
         }
 
-        None
+        commits
+    }
+
+    /// Look up specific commits by hash in one repository (used by git hooks)
+    pub fn lookup_commits(
+        repo: &Repository,
+        authors: Vec<String>,
+        hashes: &[String],
+    ) -> Vec<DetectedCommit> {
+        let project_id = Self::get_project_id(repo);
+        let project_name = Self::get_project_name(repo);
+        let identities = AuthorFilter::new(authors).identities_for(repo);
+
+        hashes
+            .iter()
+            .filter_map(|hash| git2::Oid::from_str(hash).ok())
+            .filter_map(|oid| repo.find_commit(oid).ok())
+            .map(|commit| {
+                Self::detect_commit(repo, &commit, &identities, &project_id, &project_name)
+            })
+            .collect()
+    }
+
+    /// Build a detected commit from a git commit object
+    /// Diff stats are only computed for the user's own commits; teammates' commits
+    /// just count as activity.
+    fn detect_commit(
+        repo: &Repository,
+        commit: &git2::Commit,
+        identities: &[String],
+        project_id: &str,
+        project_name: &str,
+    ) -> DetectedCommit {
+        let author = commit.author();
+        let author_name = author.name().unwrap_or("").to_string();
+        let author_email = author.email().unwrap_or("").to_string();
+        let is_own = AuthorFilter::matches(identities, &author_name, &author_email);
+
+        DetectedCommit {
+            commit_hash: commit.id().to_string(),
+            authored_at: git_time_to_local(author.when()),
+            committed_at: git_time_to_local(commit.time()),
+            detected_at: Local::now(),
+            is_own,
+            author_name,
+            author_email,
+            project_id: project_id.to_string(),
+            project_name: project_name.to_string(),
+            diff: if is_own {
+                DiffStats::for_commit(repo, commit)
+            } else {
+                None
+            },
+            conventional: commit.message().and_then(parse_conventional),
+            markers: None,
+        }
     }
 
     /// Get the project identifier (remote URL or absolute path)
@@ -317,13 +1332,43 @@ impl GitTracker {
         GitStats {
             in_git_repo: true,
             repo_count: self.repos.len(),
-            repo_names: self.repo_names(),
+            repos: self.repo_infos(),
         }
     }
 
-    /// Get the paths to all .git directories (for file watching)
-    pub fn git_dirs(&self) -> Vec<PathBuf> {
-        self.repos.iter().map(|r| r.path().to_path_buf()).collect()
+    /// Get the working directory and git directory of every repository
+    pub fn repo_paths(&self) -> Vec<(PathBuf, PathBuf)> {
+        self.repos
+            .iter()
+            .map(|r| {
+                let git_dir = r.path().to_path_buf();
+                let work_dir = r.workdir().map(|p| p.components().collect::<PathBuf>());
+                (work_dir.unwrap_or_else(|| git_dir.clone()), git_dir)
+            })
+            .collect()
+    }
+
+    /// Get the paths to watch for HEAD and ref changes
+    pub fn watch_paths(&self) -> Vec<WatchPath> {
+        let mut paths: Vec<WatchPath> = Vec::new();
+        let mut add = |path: PathBuf, recursive: bool| {
+            if !paths.iter().any(|p| p.path == path) {
+                paths.push(WatchPath { path, recursive });
+            }
+        };
+
+        for repo in &self.repos {
+            // HEAD and packed-refs are replaced by renaming a lock file over them,
+            // so watch the directories holding them rather than the files
+            add(repo.path().to_path_buf(), false);
+            add(repo.path().join("logs"), false);
+
+            let common = common_dir(repo);
+            add(common.clone(), false);
+            add(common.join("refs"), true);
+        }
+
+        paths
     }
 }
 
@@ -332,3 +1377,740 @@ impl Default for GitTracker {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::fixtures::{commit_on, init_repo, signature};
+    use git2::Signature;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn commit(repo: &Repository, message: &str) -> git2::Oid {
+        let parent = repo.head().ok().and_then(|h| h.target());
+        commit_on(repo, Some("HEAD"), parent, message)
+    }
+
+    fn tracker_for(dir: &TempDir) -> GitTracker {
+        let options = DiscoveryOptions {
+            repos: vec![dir.path().to_path_buf()],
+            ..Default::default()
+        };
+        GitTracker::discover(&options, Vec::new())
+    }
+
+    fn check_all(tracker: &mut GitTracker) -> Vec<HeadChange> {
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+        tracker.check_repos(&git_dirs)
+    }
+
+    fn hashes(change: &HeadChange) -> Vec<String> {
+        change
+            .commits
+            .iter()
+            .map(|c| c.commit_hash.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_new_commit_advances_head() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+
+        let second = commit(&repo, "second");
+        let changes = check_all(&mut tracker);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Advanced);
+        assert_eq!(hashes(&changes[0]), vec![second.to_string()]);
+        assert!(changes[0].commits[0].is_own);
+        assert!(check_all(&mut tracker).is_empty());
+    }
+
+    #[test]
+    fn test_markers_are_counted_once_indexed() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+
+        // HEAD moves don't scan the whole tree on the caller's thread
+        commit(&repo, "second");
+        assert_eq!(check_all(&mut tracker)[0].commits[0].markers, None);
+
+        let unindexed = tracker.unindexed_repos();
+        assert_eq!(unindexed.len(), 1);
+        tracker.set_marker_indexes(MarkerIndex::build_all(&unindexed));
+        assert!(tracker.unindexed_repos().is_empty());
+
+        commit(&repo, "third");
+        let changes = check_all(&mut tracker);
+        assert_eq!(changes[0].commits[0].markers, Some(MarkerCount::default()));
+    }
+
+    /// Credit the user's reported commits to `state` like the app does, returning how
+    /// many were new
+    fn credit(state: &mut crate::state::AppState, change: &HeadChange) -> u32 {
+        let own = change.commits.iter().filter(|c| c.is_own).cloned();
+        state.import_commits(
+            own.map(crate::state::TrackedCommit::from),
+            &crate::state::Rules::default(),
+        )
+    }
+
+    /// Create a commit on top of `parent` authored by `author`, without moving any ref
+    fn commit_by(
+        repo: &Repository,
+        author: &Signature,
+        parent: git2::Oid,
+        message: &str,
+    ) -> git2::Oid {
+        let tree_id = repo.treebuilder(None).unwrap().write().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.find_commit(parent).unwrap();
+        repo.commit(None, author, &signature(), message, &tree, &[&parent])
+            .unwrap()
+    }
+
+    #[test]
+    fn test_amend_is_reported_as_rewrite() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+        let mut state = crate::state::AppState::default();
+
+        commit(&repo, "typo");
+        assert_eq!(credit(&mut state, &check_all(&mut tracker)[0]), 1);
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        let amended = head
+            .amend(Some("HEAD"), None, None, None, Some("fixed"), None)
+            .unwrap();
+        let changes = check_all(&mut tracker);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Rewritten);
+        assert_eq!(hashes(&changes[0]), vec![amended.to_string()]);
+        // The amended commit is the same work, not a second commit
+        assert_eq!(credit(&mut state, &changes[0]), 0);
+        assert_eq!(state.commit_history.len(), 1);
+    }
+
+    #[test]
+    fn test_rebase_reports_rebased_commits_in_order() {
+        let (dir, repo) = init_repo();
+        let base = commit(&repo, "base");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let mut tracker = tracker_for(&dir);
+        let mut state = crate::state::AppState::default();
+
+        let at = |seconds: i64| {
+            let time = git2::Time::new(Local::now().timestamp() - 600 + seconds, 0);
+            Signature::new("Me", "me@example.com", &time).unwrap()
+        };
+        let feature_1 = commit_by(&repo, &at(1), base, "feature 1");
+        let feature_2 = commit_by(&repo, &at(2), feature_1, "feature 2");
+        repo.reference(&branch, feature_2, true, "commit").unwrap();
+        assert_eq!(credit(&mut state, &check_all(&mut tracker)[0]), 2);
+
+        // `git pull --rebase`: replay both commits, keeping their authorship, on top of
+        // a teammate's commit
+        let teammate = Signature::new("Other", "other@example.com", &at(3).when()).unwrap();
+        let upstream = commit_by(&repo, &teammate, base, "upstream");
+        let rebased_1 = commit_by(&repo, &at(1), upstream, "feature 1");
+        let rebased_2 = commit_by(&repo, &at(2), rebased_1, "feature 2");
+        repo.reference(&branch, rebased_2, true, "rebase").unwrap();
+        let changes = check_all(&mut tracker);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Rewritten);
+        assert_eq!(
+            hashes(&changes[0]),
+            vec![
+                upstream.to_string(),
+                rebased_1.to_string(),
+                rebased_2.to_string()
+            ]
+        );
+        // The rebased commits aren't credited a second time
+        assert_eq!(credit(&mut state, &changes[0]), 0);
+        assert_eq!(state.commit_history.len(), 2);
+        assert_eq!(state.total_commits_tracked, 2);
+    }
+
+    #[test]
+    fn test_new_branch_and_commit_in_one_check() {
+        let (dir, repo) = init_repo();
+        let base = commit(&repo, "initial");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let mut tracker = tracker_for(&dir);
+
+        // `git checkout -b topic && git commit` within one debounce window
+        let topic = commit_on(&repo, Some("refs/heads/topic"), Some(base), "on topic");
+        repo.set_head("refs/heads/topic").unwrap();
+        let changes = check_all(&mut tracker);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Advanced);
+        assert_eq!(hashes(&changes[0]), vec![topic.to_string()]);
+
+        // Switching back to the older branch doesn't feed
+        repo.set_head(&branch).unwrap();
+        let changes = check_all(&mut tracker);
+        assert_eq!(changes[0].kind, HeadMove::SwitchedBranch);
+        assert!(changes[0].commits.is_empty());
+    }
+
+    #[test]
+    fn test_reset_does_not_feed() {
+        let (dir, repo) = init_repo();
+        let first = commit(&repo, "initial");
+        commit(&repo, "second");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let mut tracker = tracker_for(&dir);
+
+        repo.reference(&branch, first, true, "reset").unwrap();
+        let changes = check_all(&mut tracker);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Rewound);
+        assert!(changes[0].commits.is_empty());
+    }
+
+    #[test]
+    fn test_commit_after_reset_only_reports_new_commit() {
+        let (dir, repo) = init_repo();
+        let first = commit(&repo, "initial");
+        commit(&repo, "second");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let mut tracker = tracker_for(&dir);
+
+        repo.reference(&branch, first, true, "reset").unwrap();
+        let changes = check_all(&mut tracker);
+        assert_eq!(changes[0].kind, HeadMove::Rewound);
+
+        // The dropped commit isn't counted again, only the one made after the reset
+        let third = commit(&repo, "third");
+        let changes = check_all(&mut tracker);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Advanced);
+        assert_eq!(hashes(&changes[0]), vec![third.to_string()]);
+    }
+
+    #[test]
+    fn test_commit_seen_from_two_worktrees_is_reported_once() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let worktrees = TempDir::new().unwrap();
+        let worktree_path = worktrees.path().join("feature");
+        repo.worktree("feature", &worktree_path, None).unwrap();
+        let mut tracker = tracker_for(&dir);
+        let sources: Vec<RepoSource> = tracker.repo_infos().into_iter().map(|r| r.source).collect();
+        assert_eq!(sources.len(), 2);
+        assert!(sources.iter().any(|s| matches!(s, RepoSource::Worktree(_))));
+
+        // Commit in the worktree, then fast-forward the main branch to it
+        let worktree_repo = Repository::open(&worktree_path).unwrap();
+        let feature = commit(&worktree_repo, "feature work");
+        repo.reference(&branch, feature, true, "merge").unwrap();
+
+        let changes = check_all(&mut tracker);
+        let reported: Vec<String> = changes.iter().flat_map(hashes).collect();
+        assert_eq!(reported, vec![feature.to_string()]);
+    }
+
+    #[test]
+    fn test_packed_refs_after_gc() {
+        let (dir, repo) = init_repo();
+        let head = commit(&repo, "initial");
+        let branch = repo.head().unwrap().name().unwrap().to_string();
+        let mut tracker = tracker_for(&dir);
+
+        // Simulate `git gc`: move the loose branch ref into packed-refs
+        fs::write(
+            repo.path().join("packed-refs"),
+            format!("# pack-refs with: peeled fully-peeled sorted \n{head} {branch}\n"),
+        )
+        .unwrap();
+        fs::remove_file(repo.path().join(&branch)).unwrap();
+        assert!(check_all(&mut tracker).is_empty());
+
+        // Committing on top of a packed ref is still detected
+        let next = commit(&repo, "after gc");
+        let changes = check_all(&mut tracker);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, HeadMove::Advanced);
+        assert_eq!(hashes(&changes[0]), vec![next.to_string()]);
+    }
+
+    #[test]
+    fn test_late_detection_keeps_author_day() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+
+        // Authored three days ago (e.g. a rebased or cherry-picked commit) but seen now
+        let authored = Local::now() - chrono::Duration::days(3);
+        let author = Signature::new(
+            "Me",
+            "me@example.com",
+            &git2::Time::new(authored.timestamp(), 0),
+        )
+        .unwrap();
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &author,
+            &signature(),
+            "late",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+
+        let changes = check_all(&mut tracker);
+        let detected = changes[0].commits[0].clone();
+        assert_eq!(detected.authored_at.timestamp(), authored.timestamp());
+        assert_eq!(detected.detected_at.date_naive(), Local::now().date_naive());
+
+        let tracked = crate::state::TrackedCommit::from(detected);
+        assert_eq!(tracked.timestamp.date_naive(), authored.date_naive());
+        assert!(
+            crate::state::get_today_by_project(&[tracked], &crate::state::Rules::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_diff_stats_count_lines_and_languages() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+
+        let mut builder = repo.treebuilder(None).unwrap();
+        for (name, contents) in [
+            ("main.rs", "fn main() {\n}\n"),
+            ("lib.rs", "pub fn f() {}\n"),
+            ("README.md", "# Title\n"),
+        ] {
+            let blob = repo.blob(contents.as_bytes()).unwrap();
+            builder.insert(name, blob, 0o100644).unwrap();
+        }
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+        let parent = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &signature(),
+            &signature(),
+            "add files",
+            &tree,
+            &[&parent],
+        )
+        .unwrap();
+
+        let changes = check_all(&mut tracker);
+        let diff = changes[0].commits[0].diff.clone().unwrap();
+        assert_eq!(diff.insertions, 4);
+        assert_eq!(diff.deletions, 0);
+        assert_eq!(diff.files_changed, 3);
+        assert_eq!(diff.languages, vec!["Rust", "Markdown"]);
+        assert_eq!(diff.size(), CommitSize::Tiny);
+    }
+
+    #[test]
+    fn test_only_own_new_commits_are_diffed() {
+        let (dir, repo) = init_repo();
+        let mine = commit(&repo, "mine");
+        let tree = repo.head().unwrap().peel_to_tree().unwrap();
+        let parent = repo.find_commit(mine).unwrap();
+        let teammate = Signature::now("Other", "other@example.com").unwrap();
+        let theirs = repo
+            .commit(
+                Some("HEAD"),
+                &teammate,
+                &teammate,
+                "theirs",
+                &tree,
+                &[&parent],
+            )
+            .unwrap();
+        let latest = commit(&repo, "latest");
+        let tracker = tracker_for(&dir);
+
+        let since = Local::now() - chrono::Duration::hours(1);
+        let commits = tracker.own_commits_since(since, &HashSet::new());
+        let hashes: HashSet<String> = commits.iter().map(|c| c.commit_hash.clone()).collect();
+        assert_eq!(
+            hashes,
+            HashSet::from([latest.to_string(), mine.to_string()])
+        );
+        assert!(commits.iter().all(|c| c.diff.is_some()));
+
+        // Commits already recorded are skipped
+        let known_hash = latest.to_string();
+        let known = HashSet::from([known_hash.as_str()]);
+        let commits = tracker.own_commits_since(since, &known);
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].commit_hash, mine.to_string());
+
+        // Teammates' commits still show up as activity, without diff stats
+        let detected = GitTracker::lookup_commits(&repo, Vec::new(), &[theirs.to_string()]);
+        assert!(!detected[0].is_own);
+        assert!(detected[0].diff.is_none());
+    }
+
+    #[test]
+    fn test_new_tags_are_detected_once() {
+        let (dir, repo) = init_repo();
+        let head = commit(&repo, "initial");
+        repo.tag_lightweight("v0.1.0", &repo.find_object(head, None).unwrap(), false)
+            .unwrap();
+        let mut tracker = tracker_for(&dir);
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+
+        let target = repo.find_object(head, None).unwrap();
+        repo.tag(
+            "v1.0.0",
+            &target,
+            &signature(),
+            "First release\n\nNotes",
+            false,
+        )
+        .unwrap();
+        repo.tag_lightweight("nightly", &target, false).unwrap();
+        let someone_else = Signature::now("Other", "other@example.com").unwrap();
+        repo.tag("v1.0.1", &target, &someone_else, "Not mine", false)
+            .unwrap();
+
+        let mut tags = tracker.check_tags(&git_dirs);
+        tags.sort_by(|a, b| a.name.cmp(&b.name));
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["nightly", "v1.0.0"]);
+        assert!(!tags[0].annotated);
+        assert!(tags[1].annotated);
+        assert_eq!(tags[1].message.as_deref(), Some("First release"));
+        assert_eq!(tags[1].commit_hash, head.to_string());
+
+        assert!(tracker.check_tags(&git_dirs).is_empty());
+    }
+
+    #[test]
+    fn test_fetched_lightweight_tags_are_not_releases() {
+        let (upstream_dir, upstream) = init_repo();
+        let teammate = Signature::now("Other", "other@example.com").unwrap();
+        let tree_id = upstream.treebuilder(None).unwrap().write().unwrap();
+        let tree = upstream.find_tree(tree_id).unwrap();
+        let theirs = upstream
+            .commit(Some("HEAD"), &teammate, &teammate, "theirs", &tree, &[])
+            .unwrap();
+
+        let (dir, repo) = init_repo();
+        let mut remote = repo
+            .remote("origin", upstream_dir.path().to_str().unwrap())
+            .unwrap();
+        let mut no_tags = git2::FetchOptions::new();
+        no_tags.download_tags(git2::AutotagOption::None);
+        remote
+            .fetch(
+                &["refs/heads/*:refs/remotes/origin/*"],
+                Some(&mut no_tags),
+                None,
+            )
+            .unwrap();
+        repo.set_head_detached(theirs).unwrap();
+        let mut tracker = tracker_for(&dir);
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+
+        // Upstream tags a release, then `git fetch --tags` brings it in
+        let target = upstream.find_object(theirs, None).unwrap();
+        upstream.tag_lightweight("v2.0.0", &target, false).unwrap();
+        remote
+            .fetch(&["refs/tags/*:refs/tags/*"], None, None)
+            .unwrap();
+        assert!(repo.find_reference("refs/tags/v2.0.0").is_ok());
+        assert!(tracker.check_tags(&git_dirs).is_empty());
+
+        // Tagging a commit nobody pushed yet is the user's own release
+        let local = commit(&repo, "local");
+        let local = repo.find_object(local, None).unwrap();
+        repo.tag_lightweight("v2.1.0", &local, false).unwrap();
+        let tags = tracker.check_tags(&git_dirs);
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "v2.1.0");
+    }
+
+    #[test]
+    fn test_pushes_are_detected_through_remote_refs() {
+        let (dir, repo) = init_repo();
+        repo.remote("origin", "https://example.com/me/repo.git")
+            .unwrap();
+        let base = commit(&repo, "initial");
+        repo.reference("refs/remotes/origin/main", base, true, "fetch")
+            .unwrap();
+        let mut tracker = tracker_for(&dir);
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+        assert_eq!(tracker.get_stats().unpushed_total(), 0);
+
+        commit(&repo, "one");
+        let second = commit(&repo, "two");
+        assert!(tracker.check_pushes(&git_dirs).is_empty());
+        let stats = tracker.get_stats();
+        assert_eq!(stats.unpushed_total(), 2);
+        assert!(stats.repos[0].oldest_unpushed.is_some());
+
+        repo.reference("refs/remotes/origin/main", second, true, "push")
+            .unwrap();
+        let pushes = tracker.check_pushes(&git_dirs);
+        assert_eq!(pushes.len(), 1);
+        assert_eq!(pushes[0].commit_count, 2);
+        assert_eq!(tracker.get_stats().unpushed_total(), 0);
+
+        // Dropping an unpushed commit with a reset isn't a push
+        commit(&repo, "three");
+        tracker.check_pushes(&git_dirs);
+        let object = repo.find_object(second, None).unwrap();
+        repo.reset(&object, git2::ResetType::Soft, None).unwrap();
+        assert!(tracker.check_pushes(&git_dirs).is_empty());
+    }
+
+    #[test]
+    fn test_repos_without_remotes_have_nothing_to_push() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+        commit(&repo, "local only");
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+        assert!(tracker.check_pushes(&git_dirs).is_empty());
+        assert_eq!(tracker.get_stats().unpushed_total(), 0);
+    }
+
+    #[test]
+    fn test_unfetched_remotes_have_nothing_to_push() {
+        let (dir, repo) = init_repo();
+        repo.remote("origin", "https://example.com/me/repo.git")
+            .unwrap();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+        commit(&repo, "not pushed anywhere yet");
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+        assert!(tracker.check_pushes(&git_dirs).is_empty());
+        assert_eq!(tracker.get_stats().unpushed_total(), 0);
+
+        // The first fetch brings the remote-tracking refs and the counts with it
+        let base = repo.head().unwrap().target().unwrap();
+        repo.reference("refs/remotes/origin/main", base, true, "fetch")
+            .unwrap();
+        commit(&repo, "one");
+        assert!(tracker.check_pushes(&git_dirs).is_empty());
+        assert_eq!(tracker.get_stats().unpushed_total(), 1);
+    }
+
+    #[test]
+    fn test_working_tree_status_counts() {
+        let (dir, repo) = init_repo();
+        fs::write(dir.path().join("tracked.rs"), "fn a() {}\n").unwrap();
+        fs::write(dir.path().join("staged.rs"), "fn b() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("tracked.rs")).unwrap();
+        index.add_path(Path::new("staged.rs")).unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        repo.commit(Some("HEAD"), &signature(), &signature(), "init", &tree, &[])
+            .unwrap();
+
+        let mut tracker = tracker_for(&dir);
+        assert!(!tracker.get_stats().working_tree_total().is_dirty());
+
+        fs::write(dir.path().join("tracked.rs"), "fn a() { 1; }\n").unwrap();
+        fs::write(dir.path().join("staged.rs"), "fn b() { 2; }\n").unwrap();
+        index.add_path(Path::new("staged.rs")).unwrap();
+        index.write().unwrap();
+        fs::write(dir.path().join("new.rs"), "fn c() {}\n").unwrap();
+        fs::write(dir.path().join(".gitignore"), "*.log\n").unwrap();
+        fs::write(dir.path().join("debug.log"), "noise\n").unwrap();
+
+        let git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+        tracker.refresh_statuses(&git_dirs);
+        let status = tracker.get_stats().working_tree_total();
+        assert_eq!(status.modified, 1);
+        assert_eq!(status.staged, 1);
+        assert_eq!(status.untracked, 2); // new.rs and .gitignore
+        assert_eq!(status.changed_files(), 4);
+        assert!(status.oldest_change.is_some());
+    }
+
+    #[test]
+    fn test_untracked_files_dont_age_the_working_tree() {
+        let (dir, repo) = init_repo();
+        let last_week =
+            std::time::SystemTime::now() - std::time::Duration::from_secs(7 * 24 * 60 * 60);
+        let scratch = fs::File::create(dir.path().join("scratch.txt")).unwrap();
+        scratch.set_modified(last_week).unwrap();
+        let status = WorkingTreeStatus::for_repo(&repo);
+        assert_eq!(status.untracked, 1);
+        assert_eq!(status.oldest_change, None);
+
+        fs::write(dir.path().join("lib.rs"), "fn a() {}\n").unwrap();
+        let mut index = repo.index().unwrap();
+        index.add_path(Path::new("lib.rs")).unwrap();
+        index.write().unwrap();
+        let status = WorkingTreeStatus::for_repo(&repo);
+        assert_eq!(status.staged, 1);
+        let oldest = status.oldest_change.unwrap();
+        assert!(Local::now().signed_duration_since(oldest) < chrono::Duration::hours(1));
+    }
+
+    #[test]
+    fn test_statuses_read_elsewhere_are_stored() {
+        let (dir, _repo) = init_repo();
+        let mut tracker = tracker_for(&dir);
+        fs::write(dir.path().join("new.rs"), "fn a() {}\n").unwrap();
+        let mut git_dirs: Vec<PathBuf> = tracker.repo_paths().into_iter().map(|(_, g)| g).collect();
+
+        // Repos that stopped being tracked while the statuses were read are skipped
+        let (_other_dir, other) = init_repo();
+        git_dirs.push(other.path().to_path_buf());
+        let statuses = WorkingTreeStatus::read_all(&git_dirs);
+        assert_eq!(statuses.len(), 2);
+        tracker.set_statuses(statuses);
+        let stats = tracker.get_stats();
+        assert_eq!(stats.repos.len(), 1);
+        assert_eq!(stats.working_tree_total().untracked, 1);
+    }
+
+    #[test]
+    fn test_author_filter_matches_name_or_email_ignoring_case() {
+        let identities = vec!["Me@Example.com".to_string(), "Kani Crab".to_string()];
+        let is_me = |name, email| AuthorFilter::matches(&identities, name, email);
+        assert!(is_me("Someone", "me@example.COM"));
+        assert!(is_me("kani crab", "crab@work.com"));
+        assert!(!is_me("Other", "other@example.com"));
+        // Nothing to compare against accepts every commit
+        assert!(AuthorFilter::matches(&[], "Other", "other@example.com"));
+    }
+
+    #[test]
+    fn test_author_filter_falls_back_to_user_email() {
+        let (_dir, repo) = init_repo();
+        assert_eq!(
+            AuthorFilter::new(Vec::new()).identities_for(&repo),
+            vec!["me@example.com"]
+        );
+        // Configured identities replace the repository's user.email
+        assert_eq!(
+            AuthorFilter::new(vec!["work@example.com".to_string()]).identities_for(&repo),
+            vec!["work@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_commit_size_thresholds() {
+        let size = |insertions| {
+            DiffStats {
+                insertions,
+                ..Default::default()
+            }
+            .size()
+        };
+        assert_eq!(size(0), CommitSize::Tiny);
+        assert_eq!(size(5), CommitSize::Tiny);
+        assert_eq!(size(6), CommitSize::Small);
+        assert_eq!(size(300), CommitSize::Medium);
+        assert_eq!(size(1000), CommitSize::Large);
+        assert_eq!(size(1001), CommitSize::Huge);
+    }
+
+    #[test]
+    fn test_rescan_picks_up_added_and_removed_repos() {
+        let root = TempDir::new().unwrap();
+        let options = DiscoveryOptions {
+            roots: vec![root.path().to_path_buf()],
+            ..Default::default()
+        };
+        let mut tracker = GitTracker::discover(&options, Vec::new());
+        let names = |repos: &[RepoInfo]| -> Vec<String> {
+            repos.iter().map(|repo| repo.name.clone()).collect()
+        };
+        assert!(tracker.rescan().is_empty());
+
+        let project = root.path().join("project");
+        fs::create_dir(&project).unwrap();
+        assert!(tracker.rescan().is_empty());
+        Repository::init(&project).unwrap();
+        let change = tracker.rescan();
+        assert_eq!(names(&change.added), vec!["project"]);
+        assert!(change.removed.is_empty());
+        assert!(!tracker.has_missing_repos());
+
+        fs::remove_dir_all(project.join(".git")).unwrap();
+        assert!(tracker.has_missing_repos());
+        let change = tracker.rescan();
+        assert!(change.added.is_empty());
+        assert_eq!(names(&change.removed), vec!["project"]);
+        assert!(!tracker.has_missing_repos());
+    }
+
+    #[test]
+    fn test_repeated_watcher_misses_switch_to_polling() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let mut tracker = tracker_for(&dir);
+        let git_dir = tracker.repo_paths()[0].1.clone();
+        assert_eq!(
+            tracker.repos_watched_by(WatchMode::Events),
+            vec![git_dir.clone()]
+        );
+
+        // A late event now and then doesn't give up on the watcher
+        assert!(!tracker.record_watch_check(&git_dir, true));
+        assert!(!tracker.record_watch_check(&git_dir, true));
+        assert!(!tracker.record_watch_check(&git_dir, false));
+        assert!(!tracker.record_watch_check(&git_dir, true));
+        assert!(!tracker.record_watch_check(&git_dir, true));
+        assert_eq!(
+            tracker.repos_watched_by(WatchMode::Events),
+            vec![git_dir.clone()]
+        );
+        assert!(tracker.repos_watched_by(WatchMode::Polling).is_empty());
+
+        assert!(tracker.record_watch_check(&git_dir, true));
+        assert!(tracker.repos_watched_by(WatchMode::Events).is_empty());
+        assert_eq!(
+            tracker.repos_watched_by(WatchMode::Polling),
+            vec![git_dir.clone()]
+        );
+        assert_eq!(tracker.get_stats().repos[0].watch_mode, WatchMode::Polling);
+
+        // Switching again is not a change
+        assert!(!tracker.set_watch_mode(&git_dir, WatchMode::Polling));
+    }
+
+    #[test]
+    fn test_only_ref_changes_trigger_checks() {
+        let (dir, repo) = init_repo();
+        commit(&repo, "initial");
+        let tracker = tracker_for(&dir);
+        let git_dir = tracker.repo_paths()[0].1.clone();
+
+        for relevant in ["HEAD", "packed-refs", "logs/HEAD", "refs/heads/main"] {
+            assert_eq!(
+                tracker.repos_affected_by(&git_dir.join(relevant)),
+                vec![git_dir.clone()],
+                "{relevant}"
+            );
+        }
+        for ignored in [
+            "index",
+            "index.lock",
+            "refs/heads/main.lock",
+            "COMMIT_EDITMSG",
+        ] {
+            assert!(
+                tracker.repos_affected_by(&git_dir.join(ignored)).is_empty(),
+                "{ignored}"
+            );
+        }
+    }
+}
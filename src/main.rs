@@ -1,21 +1,25 @@
+mod config;
 mod crab;
 mod environment;
 mod git;
 mod state;
 mod ui;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 use std::env;
-use std::io::{self, stdout, Write};
+use std::io::{self, stdout, Read, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
+use config::Config;
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use state::StateManager;
+use git::{hook_commit_hashes, install_hook, uninstall_hook, GitHook, GitTracker, HookAction};
+use state::{StateHealth, StateManager, TrackedCommit};
 use ui::minigames::{
     BreakoutGame, DashGame, Game2048, Game2048Move, SnakeGame, TetrisGame, TetrisMode, VsrgGame,
 };
@@ -27,6 +31,20 @@ fn main() -> Result<()> {
     let debug_mode = args.iter().any(|arg| arg == "--debug" || arg == "-d");
     let reset_mode = args.iter().any(|arg| arg == "--reset");
 
+    // Git hooks only append to the journal, `config` only reads the config and
+    // --reset deletes the state file and journal unread; everything else reads the state file
+    if !reset_mode && !matches!(args.get(1).map(String::as_str), Some("hook" | "config")) {
+        recover_corrupt_state()?;
+    }
+
+    // Subcommands run without the TUI, and load the config only if they need it
+    match args.get(1).map(String::as_str) {
+        Some("backfill") => return handle_backfill(&args[2..], &load_config(&args)?),
+        Some("config") => return handle_config(&args),
+        Some("hook") => return handle_hook(&args),
+        _ => {}
+    }
+
     // Check for --game or -g flag
     let game_flag_index = args
         .iter()
@@ -41,7 +59,9 @@ fn main() -> Result<()> {
             None // No game name provided, show menu
         };
 
-        return handle_game_mode(game_name, debug_mode);
+        // A broken config file shouldn't keep the mini-games from starting
+        let config = load_config_or_default(&args);
+        return handle_game_mode(game_name, debug_mode, &config);
     }
 
     // Handle reset before setting up TUI
@@ -49,6 +69,8 @@ fn main() -> Result<()> {
         return handle_reset();
     }
 
+    let config = load_config(&args)?;
+
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
@@ -58,7 +80,7 @@ fn main() -> Result<()> {
     let mut terminal = Terminal::new(backend)?;
 
     // Create and run app
-    let result = run_game_selection_menu(&mut terminal, debug_mode);
+    let result = run_game_selection_menu(&mut terminal, debug_mode, &config);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -74,6 +96,69 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Load the config file and apply command line overrides
+fn load_config(args: &[String]) -> Result<Config> {
+    Ok(with_cli_args(Config::load()?, args))
+}
+
+/// Load the config, falling back to the defaults with a warning when the file
+/// can't be used (for commands that must keep working with a broken config)
+fn load_config_or_default(args: &[String]) -> Config {
+    let config = Config::load().unwrap_or_else(|err| {
+        eprintln!("Warning: {:#}; using the default settings", err);
+        Config::default()
+    });
+    with_cli_args(config, args)
+}
+
+/// Apply `--repo` flags to a loaded config
+fn with_cli_args(mut config: Config, args: &[String]) -> Config {
+    config.cli_repos = flag_values(args, "--repo")
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    config
+}
+
+/// Offer to restore the newest backup when the state file can't be parsed,
+/// keeping the unreadable file aside for inspection
+fn recover_corrupt_state() -> Result<()> {
+    let state_manager = StateManager::new()?;
+    let StateHealth::Corrupt { error, backup } = state_manager.check() else {
+        return Ok(());
+    };
+
+    println!(
+        "Kanitomo's state file can't be read: {}",
+        state_manager.state_path().display()
+    );
+    println!("  {error}");
+    println!();
+    match &backup {
+        Some(backup) => print!("Restore the latest backup ({})? [Y/n] ", backup.display()),
+        None => print!("No backup is available. Start over with a fresh state? [y/N] "),
+    }
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    let input = input.trim();
+    let accepted = match backup {
+        Some(_) => !input.eq_ignore_ascii_case("n"),
+        None => input.eq_ignore_ascii_case("y"),
+    };
+    if !accepted {
+        anyhow::bail!(
+            "State file left untouched at {}",
+            state_manager.state_path().display()
+        );
+    }
+
+    let aside = state_manager.recover(backup.as_deref())?;
+    println!("The unreadable state file was kept at {}", aside.display());
+    Ok(())
+}
+
 /// Handle the --reset flag
 fn handle_reset() -> Result<()> {
     println!("This will reset all Kanitomo stats:");
@@ -99,27 +184,222 @@ fn handle_reset() -> Result<()> {
     Ok(())
 }
 
+/// Handle the `backfill` command: import recent commits from git history
+fn handle_backfill(args: &[String], config: &Config) -> Result<()> {
+    let days = match flag_value(args, "--days") {
+        Some(value) => value
+            .parse::<u32>()
+            .with_context(|| format!("Invalid value for --days: '{}'", value))?,
+        None => config.backfill_days,
+    };
+
+    let tracker = GitTracker::discover(&config.discovery_options(), config.authors.clone());
+    let stats = tracker.get_stats();
+    if !stats.in_git_repo {
+        println!("No git repositories found.");
+        return Ok(());
+    }
+
+    let state_manager = StateManager::new()?.with_rules(config.rules());
+    let mut app_state = state_manager.load()?;
+
+    let since = chrono::Local::now() - chrono::Duration::days(days as i64);
+    let commits = tracker.own_commits_since(since, &app_state.tracked_hashes());
+    let added = app_state.import_commits(
+        commits.into_iter().map(TrackedCommit::from),
+        state_manager.rules(),
+    );
+    state_manager.save(&mut app_state)?;
+
+    println!(
+        "Imported {} new commit{} from the last {} day{} across {} repo{}.",
+        added,
+        if added == 1 { "" } else { "s" },
+        days,
+        if days == 1 { "" } else { "s" },
+        stats.repo_count,
+        if stats.repo_count == 1 { "" } else { "s" },
+    );
+    println!(
+        "Current streak: {} day{}",
+        app_state.current_streak,
+        if app_state.current_streak == 1 {
+            ""
+        } else {
+            "s"
+        }
+    );
+
+    Ok(())
+}
+
+/// Handle the `config` command: print the effective config and where each value comes from
+fn handle_config(args: &[String]) -> Result<()> {
+    match Config::path() {
+        Some(path) if path.exists() => println!("Config file: {}", path.display()),
+        Some(path) => println!("Config file: {} (not found)", path.display()),
+        None => println!("Config file: none (no config directory)"),
+    }
+    println!();
+
+    // Loaded after printing the path, so a broken file is easy to find
+    let config = load_config(args)?;
+
+    let values = config.values()?;
+    let width = values
+        .iter()
+        .map(|v| v.key.len() + v.value.len() + 3)
+        .max()
+        .unwrap_or(0);
+    for value in values {
+        let setting = format!("{} = {}", value.key, value.value);
+        println!("{:<width$}  # {}", setting, value.source, width = width);
+    }
+
+    Ok(())
+}
+
+/// Handle the `hook` command: manage git hooks that record commits while the TUI is closed
+fn handle_hook(all_args: &[String]) -> Result<()> {
+    let args = &all_args[2..];
+    match args.first().map(String::as_str) {
+        Some("install") => {
+            let mut hooks = vec![GitHook::PostCommit];
+            if args.iter().any(|arg| arg == "--rewrite") {
+                hooks.push(GitHook::PostRewrite);
+            }
+            let exe = env::current_exe().context("Could not locate the kanitomo executable")?;
+
+            for_each_repo(&load_config(all_args)?, |git_dir| {
+                hooks
+                    .iter()
+                    .map(|hook| Ok((*hook, install_hook(git_dir, *hook, &exe)?)))
+                    .collect()
+            })
+        }
+        Some("uninstall") => for_each_repo(&load_config(all_args)?, |git_dir| {
+            GitHook::ALL
+                .iter()
+                .map(|hook| Ok((*hook, uninstall_hook(git_dir, *hook)?)))
+                .collect()
+        }),
+        Some("record") => {
+            let Some(hook) = args.get(1).and_then(|name| GitHook::from_name(name)) else {
+                anyhow::bail!("Usage: kanitomo hook record <post-commit|post-rewrite>");
+            };
+            // Runs inside `git commit`: a broken config must not lose the commit
+            handle_hook_record(hook, &load_config_or_default(all_args))
+        }
+        _ => {
+            eprintln!("Usage: kanitomo hook <install [--rewrite] | uninstall>");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Run a hook action against every discovered repository and report the outcome
+fn for_each_repo(
+    config: &Config,
+    mut action: impl FnMut(&std::path::Path) -> Result<Vec<(GitHook, HookAction)>>,
+) -> Result<()> {
+    let repos = GitTracker::discover(&config.discovery_options(), Vec::new()).repo_paths();
+    if repos.is_empty() {
+        println!("No git repositories found.");
+        return Ok(());
+    }
+
+    for (work_dir, git_dir) in &repos {
+        println!("{}", work_dir.display());
+
+        for (hook, outcome) in action(git_dir)? {
+            let description = match outcome {
+                HookAction::Installed { chained: true } => "installed (chained to existing hook)",
+                HookAction::Installed { chained: false } => "installed",
+                HookAction::AlreadyInstalled => "already installed",
+                HookAction::Removed { restored: true } => "removed (original hook restored)",
+                HookAction::Removed { restored: false } => "removed",
+                HookAction::NotInstalled => "not installed",
+            };
+            println!("  {}: {}", hook.file_name(), description);
+        }
+    }
+
+    Ok(())
+}
+
+/// Record the commits a git hook fired for into the state journal
+fn handle_hook_record(hook: GitHook, config: &Config) -> Result<()> {
+    let mut stdin = String::new();
+    if hook == GitHook::PostRewrite {
+        io::stdin().read_to_string(&mut stdin)?;
+    }
+
+    let repo = git2::Repository::discover(".").context("Not inside a git repository")?;
+    let hashes = hook_commit_hashes(&repo, hook, &stdin);
+
+    let state_manager = StateManager::new()?;
+    for commit in GitTracker::lookup_commits(&repo, config.authors.clone(), &hashes) {
+        if commit.is_own {
+            state_manager.append_to_journal(&TrackedCommit::from(commit))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Get every value of a repeatable `--flag value` or `--flag=value` argument
+fn flag_values<'a>(args: &'a [String], flag: &str) -> Vec<&'a str> {
+    let prefix = format!("{flag}=");
+    let mut values = Vec::new();
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        if arg == flag {
+            if let Some(value) = iter.next() {
+                values.push(value.as_str());
+            }
+        } else if let Some(value) = arg.strip_prefix(&prefix) {
+            values.push(value);
+        }
+    }
+
+    values
+}
+
+/// Get the value of a `--flag value` or `--flag=value` argument
+fn flag_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let idx = args
+        .iter()
+        .position(|arg| arg == flag || arg.starts_with(&format!("{flag}=")))?;
+
+    match args[idx].split_once('=') {
+        Some((_, value)) => Some(value),
+        None => args.get(idx + 1).map(String::as_str),
+    }
+}
+
 fn run_tamagotchi(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     debug_mode: bool,
+    config: &Config,
 ) -> Result<()> {
-    let mut app = App::new(debug_mode)?;
+    let mut app = App::new(debug_mode, config.clone())?;
     app.run(terminal)?;
     Ok(())
 }
 
 /// Handle the --game flag
-fn handle_game_mode(game_name: Option<&str>, debug_mode: bool) -> Result<()> {
+fn handle_game_mode(game_name: Option<&str>, debug_mode: bool, config: &Config) -> Result<()> {
     match game_name {
-        None => run_game_selection_menu_with_setup(debug_mode),
-        Some("kanitomo") => run_standalone_game("kanitomo", debug_mode),
-        Some("crabcatch") => run_standalone_game("crabcatch", debug_mode),
-        Some("snake") => run_standalone_game("snake", debug_mode),
-        Some("breakout") => run_standalone_game("breakout", debug_mode),
-        Some("tetris") => run_standalone_game("tetris", debug_mode),
-        Some("dash") => run_standalone_game("dash", debug_mode),
-        Some("2048") => run_standalone_game("2048", debug_mode),
-        Some("vsrg") => run_standalone_game("vsrg", debug_mode),
+        None => run_game_selection_menu_with_setup(debug_mode, config),
+        Some("kanitomo") => run_standalone_game("kanitomo", debug_mode, config),
+        Some("crabcatch") => run_standalone_game("crabcatch", debug_mode, config),
+        Some("snake") => run_standalone_game("snake", debug_mode, config),
+        Some("breakout") => run_standalone_game("breakout", debug_mode, config),
+        Some("tetris") => run_standalone_game("tetris", debug_mode, config),
+        Some("dash") => run_standalone_game("dash", debug_mode, config),
+        Some("2048") => run_standalone_game("2048", debug_mode, config),
+        Some("vsrg") => run_standalone_game("vsrg", debug_mode, config),
         Some(invalid) => {
             eprintln!(
                 "Unknown game '{}'. Available games: kanitomo, crabcatch, snake, breakout, tetris, dash, 2048, vsrg",
@@ -150,7 +430,7 @@ enum StandaloneState {
 }
 
 /// Run the game selection menu
-fn run_game_selection_menu_with_setup(debug_mode: bool) -> Result<()> {
+fn run_game_selection_menu_with_setup(debug_mode: bool, config: &Config) -> Result<()> {
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
@@ -159,7 +439,7 @@ fn run_game_selection_menu_with_setup(debug_mode: bool) -> Result<()> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run_game_selection_menu(&mut terminal, debug_mode);
+    let result = run_game_selection_menu(&mut terminal, debug_mode, config);
 
     // Restore terminal
     disable_raw_mode()?;
@@ -173,12 +453,13 @@ fn run_game_selection_menu_with_setup(debug_mode: bool) -> Result<()> {
 fn run_game_selection_menu(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     debug_mode: bool,
+    config: &Config,
 ) -> Result<()> {
-    run_standalone_game_loop(terminal, StandaloneState::GameMenu, debug_mode)
+    run_standalone_game_loop(terminal, StandaloneState::GameMenu, debug_mode, config)
 }
 
 /// Run a specific game directly
-fn run_standalone_game(game_name: &str, debug_mode: bool) -> Result<()> {
+fn run_standalone_game(game_name: &str, debug_mode: bool, config: &Config) -> Result<()> {
     // Set up terminal
     enable_raw_mode()?;
     let mut stdout = stdout();
@@ -192,8 +473,8 @@ fn run_standalone_game(game_name: &str, debug_mode: bool) -> Result<()> {
     let bounds = (size.width, size.height);
 
     let result = if game_name == "kanitomo" {
-        run_tamagotchi(&mut terminal, debug_mode)?;
-        run_standalone_game_loop(&mut terminal, StandaloneState::GameMenu, debug_mode)
+        run_tamagotchi(&mut terminal, debug_mode, config)?;
+        run_standalone_game_loop(&mut terminal, StandaloneState::GameMenu, debug_mode, config)
     } else {
         let initial_state = match game_name {
             "crabcatch" => StandaloneState::PlayingCrabCatch(CrabCatchGame::new(bounds)),
@@ -206,7 +487,7 @@ fn run_standalone_game(game_name: &str, debug_mode: bool) -> Result<()> {
             _ => unreachable!(),
         };
 
-        run_standalone_game_loop(&mut terminal, initial_state, debug_mode)
+        run_standalone_game_loop(&mut terminal, initial_state, debug_mode, config)
     };
 
     // Restore terminal
@@ -222,8 +503,9 @@ fn run_standalone_game_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
     initial_state: StandaloneState,
     debug_mode: bool,
+    config: &Config,
 ) -> Result<()> {
-    let state_manager = StateManager::new()?;
+    let state_manager = StateManager::new()?.with_rules(config.rules());
     let mut app_state = state_manager.load()?;
     let mut current_state = initial_state;
     let mut last_update = Instant::now();
@@ -298,7 +580,7 @@ fn run_standalone_game_loop(
                 match &mut current_state {
                     StandaloneState::GameMenu => match key.code {
                         KeyCode::Char('1') => {
-                            run_tamagotchi(terminal, debug_mode)?;
+                            run_tamagotchi(terminal, debug_mode, config)?;
                             app_state = state_manager.load()?;
                             last_update = Instant::now();
                             last_size = terminal.size()?;
@@ -306,10 +588,9 @@ fn run_standalone_game_loop(
                         }
                         KeyCode::Char('2') => {
                             let size = terminal.size()?;
-                            current_state = StandaloneState::PlayingCrabCatch(CrabCatchGame::new((
-                                size.width,
-                                size.height,
-                            )));
+                            current_state = StandaloneState::PlayingCrabCatch(CrabCatchGame::new(
+                                (size.width, size.height),
+                            ));
                         }
                         KeyCode::Char('3') => {
                             let size = terminal.size()?;
@@ -553,7 +834,7 @@ fn run_standalone_game_loop(
                     if app_state.minigame_best_scores.len() > 100 {
                         app_state.minigame_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowCrabCatchResults(score);
                 }
             }
@@ -567,7 +848,7 @@ fn run_standalone_game_loop(
                     if app_state.snake_best_scores.len() > 100 {
                         app_state.snake_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowSnakeResults(score);
                 }
             }
@@ -582,7 +863,7 @@ fn run_standalone_game_loop(
                     if app_state.breakout_best_scores.len() > 100 {
                         app_state.breakout_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowBreakoutResults(score, victory);
                 }
             }
@@ -634,7 +915,7 @@ fn run_standalone_game_loop(
                         }
                     }
 
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowTetrisResults(mode, score, time);
                 }
             }
@@ -647,7 +928,7 @@ fn run_standalone_game_loop(
                     if app_state.dash_best_scores.len() > 100 {
                         app_state.dash_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowDashResults(score);
                 }
             }
@@ -660,7 +941,7 @@ fn run_standalone_game_loop(
                     if app_state.game_2048_best_scores.len() > 100 {
                         app_state.game_2048_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::Show2048Results(score, max_tile);
                 }
             }
@@ -675,7 +956,7 @@ fn run_standalone_game_loop(
                     if app_state.vsrg_best_scores.len() > 100 {
                         app_state.vsrg_best_scores.truncate(100);
                     }
-                    state_manager.save(&app_state)?;
+                    state_manager.save(&mut app_state)?;
                     current_state = StandaloneState::ShowVsrgResults(score, accuracy, max_combo);
                 }
             }
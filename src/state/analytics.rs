@@ -0,0 +1,208 @@
+use super::rules::Rules;
+use super::TrackedCommit;
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, Timelike, Weekday};
+use std::collections::BTreeMap;
+
+/// Commits needed before Kani calls someone a morning or night coder
+const MIN_COMMITS_FOR_CHRONOTYPE: u32 = 10;
+
+/// Whether most commits happen early or late in the day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chronotype {
+    /// Most commits between 5am and noon
+    MorningCoder,
+    /// Most commits between 8pm and 5am
+    NightCoder,
+}
+
+impl std::fmt::Display for Chronotype {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chronotype::MorningCoder => write!(f, "morning coder"),
+            Chronotype::NightCoder => write!(f, "night coder"),
+        }
+    }
+}
+
+/// When commits happen, computed from `TrackedCommit.timestamp`
+#[derive(Debug, Clone, Default)]
+pub struct CommitAnalytics {
+    /// Commits per hour of day (0-23)
+    pub by_hour: [u32; 24],
+    /// Commits per weekday, Monday first
+    pub by_weekday: [u32; 7],
+    /// Median time between consecutive commits
+    pub median_gap: Option<Duration>,
+    /// Average time of the first commit on days with commits
+    pub average_first_commit: Option<NaiveTime>,
+    /// Average time of the last commit on days with commits
+    pub average_last_commit: Option<NaiveTime>,
+    /// Morning or night coder (None when undecided or not enough commits)
+    pub chronotype: Option<Chronotype>,
+}
+
+impl CommitAnalytics {
+    /// Compute analytics over a commit history, bucketing days with `rules`
+    pub fn from_history(history: &[TrackedCommit], rules: &Rules) -> Self {
+        let mut analytics = Self::default();
+        if history.is_empty() {
+            return analytics;
+        }
+
+        let mut timestamps: Vec<_> = history.iter().map(|c| c.timestamp).collect();
+        timestamps.sort();
+
+        // (first, last) commit per day
+        let mut days: BTreeMap<NaiveDate, (DateTime<Local>, DateTime<Local>)> = BTreeMap::new();
+        for timestamp in &timestamps {
+            let date = rules.day_of(timestamp);
+            analytics.by_hour[timestamp.hour() as usize] += 1;
+            analytics.by_weekday[date.weekday().num_days_from_monday() as usize] += 1;
+
+            let day = days.entry(date).or_insert((*timestamp, *timestamp));
+            day.0 = day.0.min(*timestamp);
+            day.1 = day.1.max(*timestamp);
+        }
+
+        let mut gaps: Vec<Duration> = timestamps.windows(2).map(|w| w[1] - w[0]).collect();
+        gaps.sort();
+        analytics.median_gap = gaps.get(gaps.len() / 2).copied();
+
+        let day_start = rules.day_start;
+        analytics.average_first_commit =
+            average_time(days.values().map(|(first, _)| first.time()), day_start);
+        analytics.average_last_commit =
+            average_time(days.values().map(|(_, last)| last.time()), day_start);
+
+        let morning: u32 = analytics.by_hour[5..12].iter().sum();
+        let night: u32 = analytics.by_hour[20..].iter().sum::<u32>()
+            + analytics.by_hour[..5].iter().sum::<u32>();
+        if timestamps.len() as u32 >= MIN_COMMITS_FOR_CHRONOTYPE && morning != night {
+            analytics.chronotype = Some(if morning > night {
+                Chronotype::MorningCoder
+            } else {
+                Chronotype::NightCoder
+            });
+        }
+
+        analytics
+    }
+
+    /// Total number of commits analyzed
+    pub fn total(&self) -> u32 {
+        self.by_hour.iter().sum()
+    }
+
+    /// Hour of day with the most commits
+    pub fn peak_hour(&self) -> Option<u32> {
+        (0..24u32)
+            .filter(|hour| self.by_hour[*hour as usize] > 0)
+            .max_by_key(|hour| (self.by_hour[*hour as usize], std::cmp::Reverse(*hour)))
+    }
+
+    /// Weekday with the most commits
+    pub fn busiest_weekday(&self) -> Option<Weekday> {
+        (0..7u8)
+            .filter(|day| self.by_weekday[*day as usize] > 0)
+            .max_by_key(|day| (self.by_weekday[*day as usize], std::cmp::Reverse(*day)))
+            .and_then(|day| Weekday::try_from(day).ok())
+    }
+}
+
+/// Average a set of times of day, counted from the start of the day so that
+/// commits after midnight average with the evening before them
+fn average_time(times: impl Iterator<Item = NaiveTime>, day_start: NaiveTime) -> Option<NaiveTime> {
+    let (sum, count) = times.fold((0i64, 0i64), |(sum, count), time| {
+        let since_start = (time - day_start).num_seconds().rem_euclid(24 * 60 * 60);
+        (sum + since_start, count + 1)
+    });
+    if count == 0 {
+        return None;
+    }
+    Some(day_start + Duration::seconds(sum / count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::fixtures::tracked_commit;
+    use chrono::{Local, TimeZone};
+
+    fn commit_at(day: u32, hour: u32, minute: u32) -> TrackedCommit {
+        tracked_commit(
+            Local
+                .with_ymd_and_hms(2026, 3, day, hour, minute, 0)
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn test_histograms_and_daily_times() {
+        // Mon Mar 2 and Tue Mar 3, 2026
+        let history = vec![
+            commit_at(2, 9, 0),
+            commit_at(2, 10, 0),
+            commit_at(2, 10, 30),
+            commit_at(3, 11, 0),
+            commit_at(3, 17, 0),
+        ];
+        let analytics = CommitAnalytics::from_history(&history, &Rules::default());
+
+        assert_eq!(analytics.total(), 5);
+        assert_eq!(analytics.by_hour[10], 2);
+        assert_eq!(analytics.by_weekday[0], 3);
+        assert_eq!(analytics.by_weekday[1], 2);
+        assert_eq!(analytics.peak_hour(), Some(10));
+        assert_eq!(analytics.busiest_weekday(), Some(Weekday::Mon));
+        // First commits 9:00 and 11:00, last commits 10:30 and 17:00
+        assert_eq!(
+            analytics.average_first_commit,
+            NaiveTime::from_hms_opt(10, 0, 0)
+        );
+        assert_eq!(
+            analytics.average_last_commit,
+            NaiveTime::from_hms_opt(13, 45, 0)
+        );
+        // Gaps: 60m, 30m, ~24h30m, 6h -> median is the third smallest
+        assert_eq!(analytics.median_gap, Some(Duration::hours(6)));
+        // Not enough commits to decide
+        assert_eq!(analytics.chronotype, None);
+    }
+
+    #[test]
+    fn test_chronotype() {
+        let mornings: Vec<_> = (1..=10).map(|day| commit_at(day, 8, 0)).collect();
+        assert_eq!(
+            CommitAnalytics::from_history(&mornings, &Rules::default()).chronotype,
+            Some(Chronotype::MorningCoder)
+        );
+
+        let nights: Vec<_> = (1..=10)
+            .map(|day| commit_at(day, if day % 2 == 0 { 23 } else { 1 }, 0))
+            .collect();
+        assert_eq!(
+            CommitAnalytics::from_history(&nights, &Rules::default()).chronotype,
+            Some(Chronotype::NightCoder)
+        );
+
+        assert!(CommitAnalytics::from_history(&[], &Rules::default())
+            .peak_hour()
+            .is_none());
+    }
+
+    #[test]
+    fn test_average_time_counts_from_day_start() {
+        let time = |hour| NaiveTime::from_hms_opt(hour, 0, 0).unwrap();
+        let last_commits = [time(23), time(1)];
+
+        // With days starting at 4am, 1am is late evening rather than early morning
+        assert_eq!(
+            average_time(last_commits.into_iter(), time(4)),
+            Some(time(0))
+        );
+        assert_eq!(
+            average_time(last_commits.into_iter(), NaiveTime::MIN),
+            Some(time(12))
+        );
+    }
+}
@@ -0,0 +1,19 @@
+use super::TrackedCommit;
+use chrono::{DateTime, Local};
+
+/// A commit by the test author in the test project, made and detected at `timestamp`
+pub fn tracked_commit(timestamp: DateTime<Local>) -> TrackedCommit {
+    TrackedCommit {
+        timestamp,
+        committed_at: Some(timestamp),
+        detected_at: Some(timestamp),
+        commit_hash: format!("hash_{}", timestamp.timestamp()),
+        project_id: "test-project".to_string(),
+        project_name: "test".to_string(),
+        author_name: "Test".to_string(),
+        author_email: "test@example.com".to_string(),
+        diff: None,
+        conventional: None,
+        markers: None,
+    }
+}
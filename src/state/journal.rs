@@ -0,0 +1,164 @@
+use super::TrackedCommit;
+use anyhow::{Context, Result};
+use std::collections::HashSet;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Append-only log of commits recorded by git hooks while the TUI is closed
+/// Each line is one JSON-encoded `TrackedCommit`
+pub struct Journal {
+    path: PathBuf,
+}
+
+impl Journal {
+    /// Create a journal backed by the given file
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Append a commit to the journal
+    pub fn append(&self, commit: &TrackedCommit) -> Result<()> {
+        let line = serde_json::to_string(commit).context("Failed to serialize commit")?;
+        self.append_lines(&line)
+    }
+
+    /// Append one or more lines (without their trailing newline) to the journal
+    fn append_lines(&self, lines: &str) -> Result<()> {
+        let mut contents = lines.to_string();
+        contents.push('\n');
+
+        // A single write of a short line keeps concurrent appends from interleaving
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .context("Failed to open journal")?;
+        file.write_all(contents.as_bytes())
+            .context("Failed to append to journal")?;
+
+        Ok(())
+    }
+
+    /// Read every entry, skipping lines that don't parse (e.g. a torn write)
+    pub fn read(&self) -> Result<Vec<TrackedCommit>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let contents = fs::read_to_string(&self.path).context("Failed to read journal")?;
+        Ok(Self::parse(&contents))
+    }
+
+    /// Drop entries whose commits are already known (i.e. saved in the state file)
+    /// Lines that don't parse are kept as they are rather than thrown away.
+    pub fn compact(&self, known: &HashSet<&str>) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        // Move the journal aside first so hooks firing meanwhile start a fresh file
+        let compacting = self.path.with_extension("jsonl.compacting");
+        fs::rename(&self.path, &compacting).context("Failed to move journal aside")?;
+
+        let contents = fs::read_to_string(&compacting).context("Failed to read journal")?;
+        let kept: Vec<&str> = contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter(|line| {
+                serde_json::from_str::<TrackedCommit>(line)
+                    .map_or(true, |commit| !known.contains(commit.commit_hash.as_str()))
+            })
+            .collect();
+        if !kept.is_empty() {
+            self.append_lines(&kept.join("\n"))?;
+        }
+
+        fs::remove_file(&compacting).context("Failed to remove compacted journal")?;
+        Ok(())
+    }
+
+    /// Delete the journal, including a copy left behind by an interrupted compaction
+    pub fn clear(&self) -> Result<()> {
+        for path in [
+            self.path.clone(),
+            self.path.with_extension("jsonl.compacting"),
+        ] {
+            if path.exists() {
+                fs::remove_file(&path).context("Failed to delete journal")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse(contents: &str) -> Vec<TrackedCommit> {
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A journal line as a hook writes it
+    fn entry(hash: &str) -> String {
+        format!(
+            r#"{{"timestamp":"2026-03-05T10:00:00+01:00","commit_hash":"{hash}","project_id":"p","project_name":"p"}}"#
+        )
+    }
+
+    fn hashes(journal: &Journal) -> Vec<String> {
+        journal
+            .read()
+            .unwrap()
+            .into_iter()
+            .map(|commit| commit.commit_hash)
+            .collect()
+    }
+
+    #[test]
+    fn test_append_and_read_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let journal = Journal::new(dir.path().join("journal.jsonl"));
+        assert!(journal.read().unwrap().is_empty());
+
+        for hash in ["a", "b"] {
+            let commit: TrackedCommit = serde_json::from_str(&entry(hash)).unwrap();
+            journal.append(&commit).unwrap();
+        }
+        assert_eq!(hashes(&journal), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_compact_drops_known_commits_and_keeps_torn_line() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("journal.jsonl");
+        let torn = &entry("d")[..40];
+        fs::write(
+            &path,
+            format!("{}\n{}\n{}\n{}", entry("a"), entry("b"), entry("c"), torn),
+        )
+        .unwrap();
+        let journal = Journal::new(path.clone());
+        assert_eq!(hashes(&journal), vec!["a", "b", "c"]);
+
+        journal.compact(&HashSet::from(["a", "c"])).unwrap();
+
+        assert_eq!(hashes(&journal), vec!["b"]);
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("{}\n{}\n", entry("b"), torn)
+        );
+        assert!(!path.with_extension("jsonl.compacting").exists());
+
+        // Later appends still parse after the kept torn line
+        let commit: TrackedCommit = serde_json::from_str(&entry("e")).unwrap();
+        journal.append(&commit).unwrap();
+        assert_eq!(hashes(&journal), vec!["b", "e"]);
+    }
+}
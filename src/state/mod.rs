@@ -1,6 +1,16 @@
+mod analytics;
+#[cfg(test)]
+pub(crate) mod fixtures;
+mod journal;
 mod persistence;
+mod rules;
 
+pub use analytics::{Chronotype, CommitAnalytics};
 pub use persistence::{
-    calculate_happiness_from_commits, calculate_streak_from_history, get_today_by_project,
-    get_today_commit_count, get_week_summary, AppState, StateManager, TrackedCommit,
+    calculate_happiness_from_commits, calculate_happiness_from_history,
+    calculate_streak_from_history, get_day_projects, get_project_summary, get_projects,
+    get_today_by_project, get_type_breakdown, get_week_languages, get_week_summary,
+    get_year_activity, AppState, ProjectSummary, ReleaseEvent, StateHealth, StateManager,
+    TrackedCommit,
 };
+pub use rules::Rules;
@@ -1,22 +1,114 @@
+use super::journal::Journal;
+use super::rules::Rules;
 use crate::environment::GroundStyle;
-use anyhow::{Context, Result};
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Weekday};
+use crate::git::{ConventionalCommit, DetectedCommit, DetectedTag, DiffStats, MarkerCount};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate};
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
-use std::fs;
-use std::path::PathBuf;
+use serde_json::{Map, Value};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 /// A commit tracked while Kanitomo was running
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrackedCommit {
-    /// When the commit was detected
+    /// When the commit was authored (older entries hold their detection time)
     pub timestamp: DateTime<Local>,
+    /// When the commit was created by git (None for older entries)
+    #[serde(default)]
+    pub committed_at: Option<DateTime<Local>>,
+    /// When Kanitomo detected the commit (None for older entries)
+    #[serde(default)]
+    pub detected_at: Option<DateTime<Local>>,
     /// Git commit hash (for deduplication)
     pub commit_hash: String,
     /// Project identifier (remote URL or absolute path)
     pub project_id: String,
     /// Project display name (folder name)
     pub project_name: String,
+    /// Author name
+    #[serde(default)]
+    pub author_name: String,
+    /// Author email
+    #[serde(default)]
+    pub author_email: String,
+    /// Lines and files changed (None for merges and older entries)
+    #[serde(default)]
+    pub diff: Option<DiffStats>,
+    /// Conventional Commits type/scope (None if the subject doesn't follow it)
+    #[serde(default)]
+    pub conventional: Option<ConventionalCommit>,
+    /// TODO/FIXME/HACK markers in the repo after the commit (None when not scanned)
+    #[serde(default)]
+    pub markers: Option<MarkerCount>,
+}
+
+impl TrackedCommit {
+    /// Whether two commits are the same change: the same hash, or the same author and
+    /// author time under a new hash (amend, rebase and cherry-pick keep both)
+    pub fn same_change(&self, other: &TrackedCommit) -> bool {
+        self.commit_hash == other.commit_hash || self.change_key() == other.change_key()
+    }
+
+    /// Author and author time, which survive rewriting a commit
+    fn change_key(&self) -> (String, i64) {
+        (self.author_email.to_lowercase(), self.timestamp.timestamp())
+    }
+}
+
+impl From<DetectedCommit> for TrackedCommit {
+    fn from(detected: DetectedCommit) -> Self {
+        Self {
+            timestamp: detected.authored_at,
+            committed_at: Some(detected.committed_at),
+            detected_at: Some(detected.detected_at),
+            commit_hash: detected.commit_hash,
+            project_id: detected.project_id,
+            project_name: detected.project_name,
+            author_name: detected.author_name,
+            author_email: detected.author_email,
+            diff: detected.diff,
+            conventional: detected.conventional,
+            markers: detected.markers,
+        }
+    }
+}
+
+/// A tag created in a watched repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseEvent {
+    /// Tag name (e.g. "v1.2.0")
+    pub tag: String,
+    /// Hash of the tagged commit
+    pub commit_hash: String,
+    /// When the tag was created (tagger time, or detection time for lightweight tags)
+    pub created_at: DateTime<Local>,
+    /// Whether the tag is annotated
+    pub annotated: bool,
+    /// Annotated tag message (first line)
+    #[serde(default)]
+    pub message: Option<String>,
+    /// Project identifier (remote URL or absolute path)
+    pub project_id: String,
+    /// Project display name (folder name)
+    pub project_name: String,
+}
+
+impl From<DetectedTag> for ReleaseEvent {
+    fn from(tag: DetectedTag) -> Self {
+        Self {
+            tag: tag.name,
+            commit_hash: tag.commit_hash,
+            created_at: tag.tagged_at,
+            annotated: tag.annotated,
+            message: tag.message,
+            project_id: tag.project_id,
+            project_name: tag.project_name,
+        }
+    }
 }
 
 /// Persistent application state
@@ -30,12 +122,21 @@ pub struct AppState {
     pub best_streak: u32,
     /// Total commits tracked across all sessions
     pub total_commits_tracked: u32,
-    /// Version for future migrations
+    /// State file format version (see `STATE_VERSION`)
     #[serde(default = "default_version")]
     pub version: u32,
     /// All commits tracked while Kanitomo was running
     #[serde(default)]
     pub commit_history: Vec<TrackedCommit>,
+    /// Recent commits by other authors seen in watched repos (team activity)
+    #[serde(default)]
+    pub team_activity: Vec<TrackedCommit>,
+    /// Tags created in watched repos (oldest first)
+    #[serde(default)]
+    pub releases: Vec<ReleaseEvent>,
+    /// Commits per language touched, across all sessions (unlocks Kani's wardrobe)
+    #[serde(default)]
+    pub language_totals: HashMap<String, u32>,
     /// Time of the last commit made while Kanitomo was open
     #[serde(default)]
     pub last_commit_time: Option<DateTime<Local>>,
@@ -94,12 +195,15 @@ impl Default for AppState {
             happiness: 50, // Start at neutral
             best_streak: 0,
             total_commits_tracked: 0,
-            version: 1,
+            version: STATE_VERSION,
             commit_history: Vec::new(),
+            team_activity: Vec::new(),
+            releases: Vec::new(),
+            language_totals: HashMap::new(),
             last_commit_time: None,
             current_streak: 0,
             ground_style: GroundStyle::random(),
-            ground_style_week: Local::now().iso_week().week(),
+            ground_style_week: Rules::default().today().iso_week().week(),
             minigame_best_scores: Vec::new(),
             snake_best_scores: Vec::new(),
             breakout_best_scores: Vec::new(),
@@ -115,9 +219,192 @@ impl Default for AppState {
     }
 }
 
+impl AppState {
+    /// Record a release, ignoring a tag already recorded for the same project
+    /// Returns whether the release is new
+    pub fn record_release(&mut self, release: ReleaseEvent) -> bool {
+        let known = self
+            .releases
+            .iter()
+            .any(|r| r.project_id == release.project_id && r.tag == release.tag);
+        if !known {
+            self.releases.push(release);
+        }
+        !known
+    }
+
+    /// Hashes of every commit in the history
+    pub fn tracked_hashes(&self) -> HashSet<&str> {
+        self.commit_history
+            .iter()
+            .map(|c| c.commit_hash.as_str())
+            .collect()
+    }
+
+    /// Import commits into the history, skipping commits that are already tracked
+    /// (see [`TrackedCommit::same_change`]). Recalculates streak, happiness and last
+    /// commit time; returns how many were added
+    pub fn import_commits(
+        &mut self,
+        commits: impl IntoIterator<Item = TrackedCommit>,
+        rules: &Rules,
+    ) -> u32 {
+        let mut known: HashSet<String> = self
+            .commit_history
+            .iter()
+            .map(|c| c.commit_hash.clone())
+            .collect();
+        let mut changes: HashSet<(String, i64)> = self
+            .commit_history
+            .iter()
+            .map(TrackedCommit::change_key)
+            .collect();
+
+        let mut added = 0u32;
+        for commit in commits {
+            if !changes.contains(&commit.change_key()) && known.insert(commit.commit_hash.clone()) {
+                changes.insert(commit.change_key());
+                self.record_languages(commit.diff.as_ref());
+                self.commit_history.push(commit);
+                added += 1;
+            }
+        }
+
+        if added > 0 {
+            self.commit_history.sort_by_key(|c| c.timestamp);
+            self.total_commits_tracked += added;
+            self.last_commit_time = self.commit_history.iter().map(|c| c.timestamp).max();
+            self.current_streak = calculate_streak_from_history(&self.commit_history, rules);
+            self.best_streak = self.best_streak.max(self.current_streak);
+            self.happiness = calculate_happiness_from_history(&self.commit_history, rules);
+        }
+
+        added
+    }
+
+    /// Count a commit toward the per-language totals
+    pub fn record_languages(&mut self, commit_diff: Option<&DiffStats>) {
+        for language in commit_diff.iter().flat_map(|diff| &diff.languages) {
+            *self.language_totals.entry(language.clone()).or_insert(0) += 1;
+        }
+    }
+
+    /// Merge in a state saved by another Kanitomo instance
+    /// Commits are unioned by hash, releases by tag, and leaderboards keep the
+    /// scores recorded on either side. Returns how many commits were new.
+    pub fn merge(&mut self, other: AppState, rules: &Rules) -> u32 {
+        let added = self.import_commits(other.commit_history, rules);
+
+        let known: HashSet<String> = self
+            .team_activity
+            .iter()
+            .map(|c| c.commit_hash.clone())
+            .collect();
+        self.team_activity.extend(
+            other
+                .team_activity
+                .into_iter()
+                .filter(|c| !known.contains(&c.commit_hash)),
+        );
+        self.team_activity.sort_by_key(|c| c.timestamp);
+
+        for release in other.releases {
+            self.record_release(release);
+        }
+        self.releases.sort_by_key(|r| r.created_at);
+
+        self.best_streak = self.best_streak.max(other.best_streak);
+
+        let leaderboards = [
+            (&mut self.minigame_best_scores, &other.minigame_best_scores),
+            (&mut self.snake_best_scores, &other.snake_best_scores),
+            (&mut self.breakout_best_scores, &other.breakout_best_scores),
+            (&mut self.tetris_normal_scores, &other.tetris_normal_scores),
+            (&mut self.tetris_zen_scores, &other.tetris_zen_scores),
+            (&mut self.tetris_dig_scores, &other.tetris_dig_scores),
+            (
+                &mut self.tetris_survival_scores,
+                &other.tetris_survival_scores,
+            ),
+            (&mut self.dash_best_scores, &other.dash_best_scores),
+            (
+                &mut self.game_2048_best_scores,
+                &other.game_2048_best_scores,
+            ),
+            (&mut self.vsrg_best_scores, &other.vsrg_best_scores),
+        ];
+        for (ours, theirs) in leaderboards {
+            merge_scores(ours, theirs, |a, b| b.cmp(a));
+        }
+        // Sprint times rank fastest first
+        merge_scores(
+            &mut self.tetris_sprint_times,
+            &other.tetris_sprint_times,
+            |a, b| a.total_cmp(b),
+        );
+
+        added
+    }
+}
+
+/// Scores kept per leaderboard
+const MAX_LEADERBOARD_ENTRIES: usize = 100;
+
+/// Add the scores only `theirs` recorded to a leaderboard (a score recorded
+/// twice on one side counts twice), then re-rank and trim it
+fn merge_scores<T: Copy + PartialEq>(
+    ours: &mut Vec<T>,
+    theirs: &[T],
+    rank: impl Fn(&T, &T) -> std::cmp::Ordering,
+) {
+    let mut missing = theirs.to_vec();
+    for score in ours.iter() {
+        if let Some(index) = missing.iter().position(|s| s == score) {
+            missing.swap_remove(index);
+        }
+    }
+    ours.extend(missing);
+    ours.sort_by(rank);
+    ours.truncate(MAX_LEADERBOARD_ENTRIES);
+}
+
+/// Number of days of team activity kept in the state file
+const TEAM_ACTIVITY_DAYS: i64 = 7;
+
+/// Number of state backups kept around (oldest are deleted first)
+const MAX_BACKUPS: usize = 5;
+
+/// Minimum time between two backups, so autosaves don't churn through them
+const BACKUP_INTERVAL_MINUTES: i64 = 60;
+
+/// Timestamp format used in backup and corrupt file names (sorts chronologically)
+const FILE_TIMESTAMP_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// Whether the state file on disk can be loaded
+#[derive(Debug)]
+pub enum StateHealth {
+    /// The state file parses (or doesn't exist yet)
+    Healthy,
+    /// The state file can't be parsed
+    Corrupt {
+        /// Why parsing failed
+        error: String,
+        /// Newest backup that parses, if any
+        backup: Option<PathBuf>,
+    },
+}
+
 /// Manages saving and loading application state
 pub struct StateManager {
     state_path: PathBuf,
+    /// Advisory lock held while saving, so instances take turns
+    lock_path: PathBuf,
+    /// Rolling backups of the state file
+    backup_dir: PathBuf,
+    /// Commits recorded by git hooks, merged in on load
+    journal: Journal,
+    /// Rules streaks and happiness are recalculated with
+    rules: Rules,
 }
 
 impl StateManager {
@@ -128,34 +415,168 @@ impl StateManager {
             .context("Could not find home directory")?
             .join(".kanitomo");
 
+        Self::in_dir(state_dir)
+    }
+
+    /// Create a state manager storing its files in `state_dir`
+    fn in_dir(state_dir: PathBuf) -> Result<Self> {
         // Create directory if it doesn't exist
         fs::create_dir_all(&state_dir).context("Failed to create kanitomo data directory")?;
 
         let state_path = state_dir.join("state.json");
+        let lock_path = state_dir.join("state.lock");
+        let backup_dir = state_dir.join("backups");
+        let journal = Journal::new(state_dir.join("journal.jsonl"));
+
+        Ok(Self {
+            state_path,
+            lock_path,
+            backup_dir,
+            journal,
+            rules: Rules::default(),
+        })
+    }
 
-        Ok(Self { state_path })
+    /// Recalculate streaks and happiness with the given rules instead of the defaults
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
     }
 
-    /// Load state from disk, applying time-based decay and recalculating streak
+    /// Rules streaks and happiness are recalculated with
+    pub fn rules(&self) -> &Rules {
+        &self.rules
+    }
+
+    /// Path of the state file
+    pub fn state_path(&self) -> &Path {
+        &self.state_path
+    }
+
+    /// Last modification time of the state file (None if it doesn't exist yet)
+    pub fn modified(&self) -> Option<SystemTime> {
+        fs::metadata(&self.state_path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    }
+
+    /// Check whether the state file can be parsed, looking for a usable backup if not
+    pub fn check(&self) -> StateHealth {
+        let error = match fs::read_to_string(&self.state_path) {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return StateHealth::Healthy,
+            Err(err) => err.to_string(),
+            Ok(contents) => match parse_state(&contents) {
+                Ok(_) => return StateHealth::Healthy,
+                // Not corrupt: `load` refuses it and leaves the file alone
+                Err(err) if err.is::<NewerStateVersion>() => return StateHealth::Healthy,
+                Err(err) => format!("{:#}", err),
+            },
+        };
+
+        let backup = self.backups().into_iter().find(|path| {
+            fs::read_to_string(path)
+                .ok()
+                .is_some_and(|contents| parse_state(&contents).is_ok())
+        });
+        StateHealth::Corrupt { error, backup }
+    }
+
+    /// Move an unreadable state file aside and restore `backup` in its place
+    /// (or start fresh when there is none). Returns where the old file was kept.
+    pub fn recover(&self, backup: Option<&Path>) -> Result<PathBuf> {
+        let _lock = self.lock()?;
+        let aside = self.state_path.with_extension(format!(
+            "json.corrupt-{}",
+            Local::now().format(FILE_TIMESTAMP_FORMAT)
+        ));
+        fs::rename(&self.state_path, &aside).context("Failed to move corrupt state file aside")?;
+
+        if let Some(backup) = backup {
+            let contents = fs::read(backup).context("Failed to read state backup")?;
+            write_atomically(&self.state_path, &contents)?;
+        }
+
+        Ok(aside)
+    }
+
+    /// Backups of the state file, newest first
+    fn backups(&self) -> Vec<PathBuf> {
+        let Ok(entries) = fs::read_dir(&self.backup_dir) else {
+            return Vec::new();
+        };
+        let mut backups: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        // Names embed the timestamp, so they sort chronologically
+        backups.sort();
+        backups.reverse();
+        backups
+    }
+
+    /// Copy the freshly saved state into the backups when the last one is old enough,
+    /// dropping the oldest beyond `MAX_BACKUPS`
+    fn backup_if_due(&self, contents: &[u8]) -> Result<()> {
+        let now = Local::now();
+        let newest = self.backups().into_iter().next().and_then(|path| {
+            let stem = path
+                .file_stem()?
+                .to_str()?
+                .strip_prefix("state-")?
+                .to_string();
+            chrono::NaiveDateTime::parse_from_str(&stem, FILE_TIMESTAMP_FORMAT).ok()
+        });
+        if newest.is_some_and(|newest| {
+            now.naive_local() - newest < Duration::minutes(BACKUP_INTERVAL_MINUTES)
+        }) {
+            return Ok(());
+        }
+
+        fs::create_dir_all(&self.backup_dir).context("Failed to create backup directory")?;
+        let path = self
+            .backup_dir
+            .join(format!("state-{}.json", now.format(FILE_TIMESTAMP_FORMAT)));
+        write_atomically(&path, contents)?;
+
+        for old in self.backups().into_iter().skip(MAX_BACKUPS) {
+            fs::remove_file(old).context("Failed to remove old state backup")?;
+        }
+        Ok(())
+    }
+
+    /// Load state from disk, migrating older formats, applying time-based decay
+    /// and recalculating streak
     pub fn load(&self) -> Result<AppState> {
         if !self.state_path.exists() {
-            return Ok(AppState::default());
+            let mut state = AppState {
+                ground_style_week: self.rules.today().iso_week().week(),
+                ..Default::default()
+            };
+            state.import_commits(self.journal.read()?, &self.rules);
+            return Ok(state);
         }
 
         let contents = fs::read_to_string(&self.state_path).context("Failed to read state file")?;
 
-        let mut state: AppState =
-            serde_json::from_str(&contents).context("Failed to parse state file")?;
+        let mut state = parse_state(&contents)
+            .with_context(|| format!("Failed to load {}", self.state_path.display()))?;
+
+        // Merge commits recorded by git hooks while the TUI was closed
+        state.import_commits(self.journal.read()?, &self.rules);
+
+        // Only keep a week of team activity around
+        let cutoff = Local::now() - Duration::days(TEAM_ACTIVITY_DAYS);
+        state.team_activity.retain(|c| c.timestamp >= cutoff);
 
         // Recalculate streak from history (may have broken since last session)
-        state.current_streak = calculate_streak_from_history(&state.commit_history);
+        state.current_streak = calculate_streak_from_history(&state.commit_history, &self.rules);
 
-        // Update happiness based on today's commit count
-        let today_commits = get_today_commit_count(&state.commit_history);
-        state.happiness = calculate_happiness_from_commits(today_commits);
+        // Update happiness based on today's commits
+        state.happiness = calculate_happiness_from_history(&state.commit_history, &self.rules);
 
         // Check if we should rotate ground style (new week)
-        let current_week = Local::now().iso_week().week();
+        let current_week = self.rules.today().iso_week().week();
         if state.ground_style_week != current_week {
             state.ground_style = GroundStyle::random();
             state.ground_style_week = current_week;
@@ -167,24 +588,78 @@ impl StateManager {
         Ok(state)
     }
 
-    /// Save state to disk
-    pub fn save(&self, state: &AppState) -> Result<()> {
-        let mut state = state.clone();
+    /// Merge the state another instance saved into `state`
+    /// Returns how many commits were picked up
+    pub fn merge_from_disk(&self, state: &mut AppState) -> Result<u32> {
+        Ok(match self.read_saved()? {
+            Some(saved) => state.merge(saved, &self.rules),
+            None => 0,
+        })
+    }
+
+    /// Parse the state file as it is on disk, without `load`'s bookkeeping
+    /// (None if it doesn't exist yet)
+    fn read_saved(&self) -> Result<Option<AppState>> {
+        match fs::read_to_string(&self.state_path) {
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err).context("Failed to read state file"),
+            Ok(contents) => parse_state(&contents).map(Some),
+        }
+    }
+
+    /// Take the advisory lock on the state file, waiting for other instances to
+    /// release it. The lock is held until the returned file is dropped.
+    fn lock(&self) -> Result<File> {
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&self.lock_path)
+            .context("Failed to open state lock file")?;
+        file.lock().context("Failed to lock state file")?;
+        Ok(file)
+    }
+
+    /// Save state to disk, first merging in what other instances saved since
+    /// `state` was loaded. `state` is updated with the merged result.
+    pub fn save(&self, state: &mut AppState) -> Result<()> {
+        let _lock = self.lock()?;
+        match self.read_saved() {
+            Ok(Some(saved)) => {
+                state.merge(saved, &self.rules);
+            }
+            Ok(None) => {}
+            // Never overwrite the state of a newer Kanitomo
+            Err(err) if err.is::<NewerStateVersion>() => return Err(err),
+            // Nothing to salvage from an unreadable file, ours replaces it
+            Err(_) => {}
+        }
         state.last_seen = Local::now();
 
-        let contents = serde_json::to_string_pretty(&state).context("Failed to serialize state")?;
+        let contents = serde_json::to_string_pretty(state).context("Failed to serialize state")?;
 
-        fs::write(&self.state_path, contents).context("Failed to write state file")?;
+        write_atomically(&self.state_path, contents.as_bytes())?;
+        self.backup_if_due(contents.as_bytes())?;
+
+        // Journal entries now live in the state file
+        self.journal.compact(&state.tracked_hashes())?;
 
         Ok(())
     }
 
-    /// Reset state to defaults (deletes state file)
+    /// Record a commit from a git hook, to be merged on the next load
+    pub fn append_to_journal(&self, commit: &TrackedCommit) -> Result<()> {
+        self.journal.append(commit)
+    }
+
+    /// Reset state to defaults (deletes the state file and the hook journal)
     pub fn reset(&self) -> Result<()> {
+        let _lock = self.lock()?;
         if self.state_path.exists() {
             fs::remove_file(&self.state_path).context("Failed to delete state file")?;
         }
-        Ok(())
+        // Otherwise the next load would bring the journaled commits back
+        self.journal.clear()
     }
 }
 
@@ -194,31 +669,163 @@ impl Default for StateManager {
     }
 }
 
-/// Check if a weekday is a weekend day
-fn is_weekend(weekday: Weekday) -> bool {
-    matches!(weekday, Weekday::Sat | Weekday::Sun)
+/// Write a file so that readers see either the old or the new contents, never a
+/// truncated mix: write a temp file next to it, flush it to disk, then rename
+fn write_atomically(path: &Path, contents: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("json.tmp");
+    let mut file = File::create(&temp_path).context("Failed to create temporary state file")?;
+    file.write_all(contents)
+        .context("Failed to write temporary state file")?;
+    file.sync_all()
+        .context("Failed to flush temporary state file")?;
+    fs::rename(&temp_path, path).context("Failed to replace state file")?;
+    Ok(())
+}
+
+/// Current state file format version, bumped whenever a migration is added
+const STATE_VERSION: u32 = 2;
+
+/// Upgrade of the raw state JSON from one version to the next
+type Migration = fn(&mut Map<String, Value>) -> Result<()>;
+
+/// Migrations in order: `MIGRATIONS[0]` turns a version 1 file into version 2, and so on
+const MIGRATIONS: [Migration; STATE_VERSION as usize - 1] = [migrate_v1_language_totals];
+
+/// A state file written by a newer Kanitomo, which this build must not overwrite
+#[derive(Debug)]
+struct NewerStateVersion(u64);
+
+impl std::fmt::Display for NewerStateVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "it was written by a newer version of Kanitomo (state format {}, this build reads up to {}); update Kanitomo to use it, the file was left untouched",
+            self.0, STATE_VERSION
+        )
+    }
+}
+
+impl std::error::Error for NewerStateVersion {}
+
+/// Parse a state file, bringing older formats up to `STATE_VERSION` first
+fn parse_state(contents: &str) -> Result<AppState> {
+    let mut raw: Value = serde_json::from_str(contents).context("Failed to parse state file")?;
+    let Some(fields) = raw.as_object_mut() else {
+        bail!("State file is not a JSON object");
+    };
+
+    // Files from before versioning count as version 1
+    let version = fields
+        .get("version")
+        .and_then(Value::as_u64)
+        .unwrap_or(1)
+        .max(1);
+    if version > STATE_VERSION as u64 {
+        return Err(NewerStateVersion(version).into());
+    }
+
+    for (from, migrate) in MIGRATIONS.iter().enumerate().skip(version as usize - 1) {
+        migrate(fields)
+            .with_context(|| format!("Failed to migrate state file from version {}", from + 1))?;
+    }
+    fields.insert("version".to_string(), STATE_VERSION.into());
+
+    serde_json::from_value(raw).context("Failed to parse state file")
+}
+
+/// v1 -> v2: fill in per-language totals (added after v1 files were written)
+/// from the languages recorded in each commit's diff stats
+fn migrate_v1_language_totals(state: &mut Map<String, Value>) -> Result<()> {
+    let has_totals = state
+        .get("language_totals")
+        .and_then(Value::as_object)
+        .is_some_and(|totals| !totals.is_empty());
+    if has_totals {
+        return Ok(());
+    }
+
+    let mut totals: HashMap<String, u32> = HashMap::new();
+    let commits = state.get("commit_history").and_then(Value::as_array);
+    for commit in commits.into_iter().flatten() {
+        let languages = commit
+            .pointer("/diff/languages")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str);
+        for language in languages {
+            *totals.entry(language.to_string()).or_insert(0) += 1;
+        }
+    }
+    state.insert(
+        "language_totals".to_string(),
+        serde_json::to_value(totals).context("Failed to serialize language totals")?,
+    );
+    Ok(())
 }
 
 /// Get total commits for today across all projects
-pub fn get_today_commit_count(history: &[TrackedCommit]) -> u32 {
-    let today = Local::now().date_naive();
+pub fn get_today_commit_count(history: &[TrackedCommit], rules: &Rules) -> u32 {
+    let today = rules.today();
+    history
+        .iter()
+        .filter(|commit| rules.day_of(&commit.timestamp) == today)
+        .count() as u32
+}
+
+/// Get commits per language touched this week (Monday to today), most used first
+pub fn get_week_languages(history: &[TrackedCommit], rules: &Rules) -> Vec<(String, u32)> {
+    let today = rules.today();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for commit in history
+        .iter()
+        .filter(|c| rules.day_of(&c.timestamp) >= monday)
+    {
+        for language in commit.diff.iter().flat_map(|diff| &diff.languages) {
+            *counts.entry(language.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut languages: Vec<(String, u32)> = counts.into_iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    languages
+}
+
+/// Count today's commits that removed TODO/FIXME/HACK markers
+pub fn get_today_cleanup_count(history: &[TrackedCommit], rules: &Rules) -> u32 {
+    let today = rules.today();
     history
         .iter()
-        .filter(|commit| commit.timestamp.date_naive() == today)
+        .filter(|commit| rules.day_of(&commit.timestamp) == today)
+        .filter(|commit| commit.markers.is_some_and(|m| m.change < 0))
         .count() as u32
 }
 
+/// Calculate happiness from today's commits, with a small bonus for
+/// each commit that cleaned up TODO/FIXME/HACK markers
+pub fn calculate_happiness_from_history(history: &[TrackedCommit], rules: &Rules) -> u8 {
+    const CLEANUP_BONUS: u32 = 3;
+
+    let happiness = calculate_happiness_from_commits(get_today_commit_count(history, rules), rules)
+        as u32
+        + get_today_cleanup_count(history, rules) * CLEANUP_BONUS;
+    happiness.min(100) as u8
+}
+
 /// Calculate happiness from today's commit count
-pub fn calculate_happiness_from_commits(commits: u32) -> u8 {
-    const MAX_COMMITS: f32 = 20.0;
+/// Full happiness takes `happiness.full_at_commits` commits (see the config)
+pub fn calculate_happiness_from_commits(commits: u32, rules: &Rules) -> u8 {
     const CURVE_STEEPNESS: f32 = 4.0;
 
     if commits == 0 {
         return 0;
     }
 
-    let capped = (commits as f32).min(MAX_COMMITS);
-    let x = capped / MAX_COMMITS;
+    let max_commits = rules.full_happiness_commits.max(1) as f32;
+    let capped = (commits as f32).min(max_commits);
+    let x = capped / max_commits;
     let numerator = 1.0 - (-CURVE_STEEPNESS * x).exp();
     let denominator = 1.0 - (-CURVE_STEEPNESS).exp();
     let normalized = if denominator > 0.0 {
@@ -232,23 +839,24 @@ pub fn calculate_happiness_from_commits(commits: u32) -> u8 {
 /// Calculate streak from commit history
 /// Rules:
 /// - Weekdays require a commit to continue the streak
-/// - Weekends are optional bonus days (don't break streak, but extend if committed)
+/// - Weekends (`schedule.weekend_days` in the config) are optional bonus days
+///   (don't break streak, but extend if committed)
 /// - Missing a weekday resets streak to 0
-pub fn calculate_streak_from_history(history: &[TrackedCommit]) -> u32 {
+pub fn calculate_streak_from_history(history: &[TrackedCommit], rules: &Rules) -> u32 {
     if history.is_empty() {
         return 0;
     }
 
-    let today = Local::now().date_naive();
+    let today = rules.today();
     let commit_dates: HashSet<NaiveDate> =
-        history.iter().map(|c| c.timestamp.date_naive()).collect();
+        history.iter().map(|c| rules.day_of(&c.timestamp)).collect();
 
     let mut streak = 0u32;
     let mut check_date = today;
 
     // First, check if we have a commit today or if today is a weekend
     // If it's a weekday with no commit, streak hasn't started today
-    if !commit_dates.contains(&check_date) && !is_weekend(check_date.weekday()) {
+    if !commit_dates.contains(&check_date) && !rules.is_weekend(check_date.weekday()) {
         // Check if we had commits yesterday or recently
         check_date = match check_date.pred_opt() {
             Some(d) => d,
@@ -261,7 +869,7 @@ pub fn calculate_streak_from_history(history: &[TrackedCommit]) -> u32 {
         if commit_dates.contains(&check_date) {
             // Committed on this day - counts toward streak
             streak += 1;
-        } else if is_weekend(check_date.weekday()) {
+        } else if rules.is_weekend(check_date.weekday()) {
             // Weekend with no commit - that's fine, skip it
         } else {
             // Weekday with no commit - streak broken
@@ -284,14 +892,15 @@ pub fn calculate_streak_from_history(history: &[TrackedCommit]) -> u32 {
 }
 
 /// Get commits grouped by project for today
-pub fn get_today_by_project(history: &[TrackedCommit]) -> Vec<(String, String, u32)> {
-    use std::collections::HashMap;
-
-    let today = Local::now().date_naive();
+pub fn get_today_by_project(
+    history: &[TrackedCommit],
+    rules: &Rules,
+) -> Vec<(String, String, u32)> {
+    let today = rules.today();
     let mut by_project: HashMap<String, (String, u32)> = HashMap::new();
 
     for commit in history {
-        if commit.timestamp.date_naive() == today {
+        if rules.day_of(&commit.timestamp) == today {
             let entry = by_project
                 .entry(commit.project_id.clone())
                 .or_insert_with(|| (commit.project_name.clone(), 0));
@@ -309,8 +918,8 @@ pub fn get_today_by_project(history: &[TrackedCommit]) -> Vec<(String, String, u
 }
 
 /// Get commits per day for the current week (Mon-Sun)
-pub fn get_week_summary(history: &[TrackedCommit]) -> Vec<(NaiveDate, u32)> {
-    let today = Local::now().date_naive();
+pub fn get_week_summary(history: &[TrackedCommit], rules: &Rules) -> Vec<(NaiveDate, u32)> {
+    let today = rules.today();
 
     // Find the Monday of this week
     let days_since_monday = today.weekday().num_days_from_monday();
@@ -326,7 +935,7 @@ pub fn get_week_summary(history: &[TrackedCommit]) -> Vec<(NaiveDate, u32)> {
 
         let count = history
             .iter()
-            .filter(|c| c.timestamp.date_naive() == date)
+            .filter(|c| rules.day_of(&c.timestamp) == date)
             .count() as u32;
 
         daily_counts.push((date, count));
@@ -335,52 +944,356 @@ pub fn get_week_summary(history: &[TrackedCommit]) -> Vec<(NaiveDate, u32)> {
     daily_counts
 }
 
+/// Get commit counts per Conventional Commits type for today and this week (Mon-Sun)
+/// Returns (type, today, week) sorted by week count; untyped commits count as "other"
+pub fn get_type_breakdown(history: &[TrackedCommit], rules: &Rules) -> Vec<(String, u32, u32)> {
+    let today = rules.today();
+    let monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    let mut by_type: HashMap<String, (u32, u32)> = HashMap::new();
+
+    for commit in history {
+        let date = rules.day_of(&commit.timestamp);
+        if date < monday || date > today {
+            continue;
+        }
+
+        let commit_type = commit
+            .conventional
+            .as_ref()
+            .map(|c| c.commit_type.clone())
+            .unwrap_or_else(|| "other".to_string());
+        let entry = by_type.entry(commit_type).or_insert((0, 0));
+        if date == today {
+            entry.0 += 1;
+        }
+        entry.1 += 1;
+    }
+
+    let mut result: Vec<_> = by_type
+        .into_iter()
+        .map(|(commit_type, (today, week))| (commit_type, today, week))
+        .collect();
+    // Sort by week count descending, then by type for stable ordering
+    result.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+    result
+}
+
+/// Get commit counts per day for a calendar year, optionally for a single project
+pub fn get_year_activity(
+    history: &[TrackedCommit],
+    year: i32,
+    project_id: Option<&str>,
+    rules: &Rules,
+) -> HashMap<NaiveDate, u32> {
+    let mut by_day = HashMap::new();
+    for commit in history {
+        let date = rules.day_of(&commit.timestamp);
+        if date.year() != year || project_id.is_some_and(|id| commit.project_id != id) {
+            continue;
+        }
+        *by_day.entry(date).or_insert(0) += 1;
+    }
+    by_day
+}
+
+/// Get the projects committed to on a day as (name, count), busiest first
+pub fn get_day_projects(
+    history: &[TrackedCommit],
+    date: NaiveDate,
+    project_id: Option<&str>,
+    rules: &Rules,
+) -> Vec<(String, u32)> {
+    let mut by_project: HashMap<&str, (&str, u32)> = HashMap::new();
+    for commit in history {
+        if rules.day_of(&commit.timestamp) != date
+            || project_id.is_some_and(|id| commit.project_id != id)
+        {
+            continue;
+        }
+        by_project
+            .entry(&commit.project_id)
+            .or_insert((&commit.project_name, 0))
+            .1 += 1;
+    }
+
+    let mut result: Vec<(String, u32)> = by_project
+        .into_values()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect();
+    result.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    result
+}
+
+/// Get every project in the history as (id, name), sorted by name
+pub fn get_projects(history: &[TrackedCommit]) -> Vec<(String, String)> {
+    let mut projects: HashMap<&str, &str> = HashMap::new();
+    for commit in history {
+        projects.insert(&commit.project_id, &commit.project_name);
+    }
+
+    let mut result: Vec<(String, String)> = projects
+        .into_iter()
+        .map(|(id, name)| (id.to_string(), name.to_string()))
+        .collect();
+    result.sort_by(|a, b| a.1.cmp(&b.1).then_with(|| a.0.cmp(&b.0)));
+    result
+}
+
+/// Activity of a single project, for the project view
+#[derive(Debug, Clone)]
+pub struct ProjectSummary {
+    /// Commits ever recorded for the project
+    pub total: u32,
+    /// Current streak counting only this project's commits
+    pub current_streak: u32,
+    /// Commits per day, oldest first, ending today
+    pub daily: Vec<(NaiveDate, u32)>,
+    /// Longest stretch without commits, as the commit days around it
+    /// (the end is today when the gap is still going on)
+    pub longest_gap: Option<(NaiveDate, NaiveDate)>,
+    /// Most recent commit time
+    pub last_commit: Option<DateTime<Local>>,
+    /// TODO/FIXME/HACK count after each scanned commit, oldest first
+    pub marker_trend: Vec<MarkerCount>,
+}
+
+impl ProjectSummary {
+    /// Number of idle days in the longest gap
+    pub fn longest_gap_days(&self) -> i64 {
+        self.longest_gap
+            .map(|(from, to)| (to - from).num_days() - 1)
+            .unwrap_or(0)
+    }
+}
+
+/// Summarize one project's history over the last `days` days
+pub fn get_project_summary(
+    history: &[TrackedCommit],
+    project_id: &str,
+    days: u32,
+    rules: &Rules,
+) -> ProjectSummary {
+    let today = rules.today();
+    let commits: Vec<TrackedCommit> = history
+        .iter()
+        .filter(|c| c.project_id == project_id)
+        .cloned()
+        .collect();
+    let dates: BTreeSet<NaiveDate> = commits.iter().map(|c| rules.day_of(&c.timestamp)).collect();
+
+    let mut scanned: Vec<&TrackedCommit> = commits.iter().filter(|c| c.markers.is_some()).collect();
+    scanned.sort_by_key(|c| c.committed_at.unwrap_or(c.timestamp));
+    let marker_trend = scanned.iter().filter_map(|c| c.markers).collect();
+
+    let daily = (0..days as i64)
+        .rev()
+        .map(|offset| {
+            let date = today - Duration::days(offset);
+            let count = commits
+                .iter()
+                .filter(|c| rules.day_of(&c.timestamp) == date)
+                .count() as u32;
+            (date, count)
+        })
+        .collect();
+
+    ProjectSummary {
+        total: commits.len() as u32,
+        current_streak: calculate_streak_from_history(&commits, rules),
+        daily,
+        longest_gap: find_longest_gap(&dates, today),
+        last_commit: commits.iter().map(|c| c.timestamp).max(),
+        marker_trend,
+    }
+}
+
+/// Find the longest run of days without commits between two commit days,
+/// or between the last commit day and today
+fn find_longest_gap(
+    dates: &BTreeSet<NaiveDate>,
+    today: NaiveDate,
+) -> Option<(NaiveDate, NaiveDate)> {
+    let last = *dates.last()?;
+    let bounds = dates.iter().copied().chain((last < today).then_some(today));
+
+    let mut longest: Option<(NaiveDate, NaiveDate)> = None;
+    let mut previous: Option<NaiveDate> = None;
+    for date in bounds {
+        if let Some(from) = previous {
+            let idle_days = (date - from).num_days() - 1;
+            let longest_days = longest.map_or(0, |(a, b)| (b - a).num_days() - 1);
+            if idle_days > longest_days {
+                longest = Some((from, date));
+            }
+        }
+        previous = Some(date);
+    }
+    longest
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use crate::state::fixtures::tracked_commit;
+    use chrono::{NaiveTime, TimeZone, Weekday};
 
     #[test]
     fn test_happiness_curve_points() {
-        assert_eq!(calculate_happiness_from_commits(0), 0);
-        assert_eq!(calculate_happiness_from_commits(2), 34);
-        assert_eq!(calculate_happiness_from_commits(5), 64);
-        assert_eq!(calculate_happiness_from_commits(10), 88);
-        assert_eq!(calculate_happiness_from_commits(15), 97);
-        assert_eq!(calculate_happiness_from_commits(20), 100);
-        assert_eq!(calculate_happiness_from_commits(25), 100);
+        assert_eq!(calculate_happiness_from_commits(0, &Rules::default()), 0);
+        assert_eq!(calculate_happiness_from_commits(2, &Rules::default()), 34);
+        assert_eq!(calculate_happiness_from_commits(5, &Rules::default()), 64);
+        assert_eq!(calculate_happiness_from_commits(10, &Rules::default()), 88);
+        assert_eq!(calculate_happiness_from_commits(15, &Rules::default()), 97);
+        assert_eq!(calculate_happiness_from_commits(20, &Rules::default()), 100);
+        assert_eq!(calculate_happiness_from_commits(25, &Rules::default()), 100);
     }
 
     #[test]
     fn test_happiness_curve_interpolation() {
-        assert_eq!(calculate_happiness_from_commits(1), 18);
-        assert_eq!(calculate_happiness_from_commits(8), 81);
-        assert_eq!(calculate_happiness_from_commits(12), 93);
-        assert_eq!(calculate_happiness_from_commits(16), 98);
-    }
-
-    fn make_commit(date: DateTime<Local>) -> TrackedCommit {
-        TrackedCommit {
-            timestamp: date,
-            commit_hash: format!("hash_{}", date.timestamp()),
-            project_id: "test-project".to_string(),
-            project_name: "test".to_string(),
-        }
+        assert_eq!(calculate_happiness_from_commits(1, &Rules::default()), 18);
+        assert_eq!(calculate_happiness_from_commits(8, &Rules::default()), 81);
+        assert_eq!(calculate_happiness_from_commits(12, &Rules::default()), 93);
+        assert_eq!(calculate_happiness_from_commits(16, &Rules::default()), 98);
+    }
+
+    #[test]
+    fn test_cleanup_commits_add_happiness() {
+        let now = Local::now();
+        let mut cleanup = tracked_commit(now);
+        cleanup.markers = Some(MarkerCount {
+            total: 4,
+            change: -2,
+        });
+        let mut added = tracked_commit(now - Duration::seconds(1));
+        added.markers = Some(MarkerCount {
+            total: 6,
+            change: 1,
+        });
+        let history = vec![added, cleanup];
+
+        assert_eq!(get_today_cleanup_count(&history, &Rules::default()), 1);
+        assert_eq!(
+            calculate_happiness_from_history(&history, &Rules::default()),
+            calculate_happiness_from_commits(2, &Rules::default()) + 3
+        );
+    }
+
+    #[test]
+    fn test_language_totals_and_week_languages() {
+        let with_languages = |date: DateTime<Local>, languages: &[&str]| {
+            let mut commit = tracked_commit(date);
+            commit.diff = Some(DiffStats {
+                languages: languages.iter().map(|l| l.to_string()).collect(),
+                ..Default::default()
+            });
+            commit
+        };
+        let now = Local::now();
+        let mut state = AppState::default();
+        state.import_commits(
+            vec![
+                with_languages(now, &["Rust", "TOML"]),
+                with_languages(now - Duration::seconds(1), &["Rust"]),
+                with_languages(now - Duration::days(14), &["Python"]),
+            ],
+            &Rules::default(),
+        );
+
+        assert_eq!(state.language_totals.get("Rust"), Some(&2));
+        assert_eq!(state.language_totals.get("Python"), Some(&1));
+        assert_eq!(
+            get_week_languages(&state.commit_history, &Rules::default()),
+            vec![("Rust".to_string(), 2), ("TOML".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    fn test_year_activity_and_day_projects() {
+        let day = Local.with_ymd_and_hms(2025, 6, 3, 12, 0, 0).unwrap();
+        let mut other = tracked_commit(day + Duration::hours(1));
+        other.project_id = "other".to_string();
+        other.project_name = "other".to_string();
+        let history = vec![
+            tracked_commit(day),
+            tracked_commit(day + Duration::minutes(5)),
+            tracked_commit(day + Duration::days(1)),
+            tracked_commit(Local.with_ymd_and_hms(2024, 6, 3, 12, 0, 0).unwrap()),
+            other,
+        ];
+
+        let activity = get_year_activity(&history, 2025, None, &Rules::default());
+        assert_eq!(activity.get(&day.date_naive()), Some(&3));
+        assert_eq!(activity.values().sum::<u32>(), 4);
+        let filtered = get_year_activity(&history, 2025, Some("other"), &Rules::default());
+        assert_eq!(filtered.values().sum::<u32>(), 1);
+
+        assert_eq!(
+            get_day_projects(&history, day.date_naive(), None, &Rules::default()),
+            vec![("test".to_string(), 2), ("other".to_string(), 1)]
+        );
+        assert_eq!(
+            get_projects(&history),
+            vec![
+                ("other".to_string(), "other".to_string()),
+                ("test-project".to_string(), "test".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_longest_gap_between_commit_days() {
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let dates: BTreeSet<NaiveDate> = [day(1), day(2), day(6), day(8)].into();
+
+        assert_eq!(find_longest_gap(&dates, day(9)), Some((day(2), day(6))));
+        // An ongoing gap counts up to today
+        assert_eq!(find_longest_gap(&dates, day(20)), Some((day(8), day(20))));
+        // Consecutive days have no gap
+        let busy: BTreeSet<NaiveDate> = [day(1), day(2), day(3)].into();
+        assert_eq!(find_longest_gap(&busy, day(3)), None);
+        assert_eq!(find_longest_gap(&BTreeSet::new(), day(3)), None);
+    }
+
+    #[test]
+    fn test_project_summary_filters_by_project() {
+        let now = Local::now();
+        let mut other = tracked_commit(now - Duration::days(1));
+        other.project_id = "other".to_string();
+        let history = vec![
+            tracked_commit(now),
+            tracked_commit(now - Duration::days(3)),
+            tracked_commit(now - Duration::days(40)),
+            other,
+        ];
+
+        let summary = get_project_summary(&history, "test-project", 30, &Rules::default());
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.daily.len(), 30);
+        assert_eq!(summary.daily.last().map(|(_, count)| *count), Some(1));
+        assert_eq!(summary.daily.iter().map(|(_, count)| count).sum::<u32>(), 2);
+        assert_eq!(summary.longest_gap_days(), 36);
+        assert_eq!(summary.last_commit, Some(now));
     }
 
     #[test]
     fn test_streak_empty_history() {
         let history: Vec<TrackedCommit> = vec![];
-        assert_eq!(calculate_streak_from_history(&history), 0);
+        assert_eq!(
+            calculate_streak_from_history(&history, &Rules::default()),
+            0
+        );
     }
 
     #[test]
     fn test_streak_single_commit_today() {
         // Single commit today should give streak of 1
         let today = Local::now();
-        let history = vec![make_commit(today)];
+        let history = vec![tracked_commit(today)];
 
-        assert_eq!(calculate_streak_from_history(&history), 1);
+        assert_eq!(
+            calculate_streak_from_history(&history, &Rules::default()),
+            1
+        );
     }
 
     #[test]
@@ -391,11 +1304,15 @@ mod tests {
         let tue = Local.with_ymd_and_hms(2026, 1, 20, 12, 0, 0).unwrap();
         let wed = Local.with_ymd_and_hms(2026, 1, 21, 12, 0, 0).unwrap();
 
-        let history = vec![make_commit(mon), make_commit(tue), make_commit(wed)];
+        let history = vec![
+            tracked_commit(mon),
+            tracked_commit(tue),
+            tracked_commit(wed),
+        ];
 
         // This test depends on current date, so we just verify it returns a value
         // In practice, the streak would be 3 if today is Wed Jan 21
-        let streak = calculate_streak_from_history(&history);
+        let streak = calculate_streak_from_history(&history, &Rules::default());
         // Just ensure the function runs without panicking
         let _ = streak;
     }
@@ -408,11 +1325,15 @@ mod tests {
         let sat = Local.with_ymd_and_hms(2026, 1, 24, 12, 0, 0).unwrap();
         let mon = Local.with_ymd_and_hms(2026, 1, 26, 12, 0, 0).unwrap();
 
-        let history = vec![make_commit(fri), make_commit(sat), make_commit(mon)];
+        let history = vec![
+            tracked_commit(fri),
+            tracked_commit(sat),
+            tracked_commit(mon),
+        ];
 
         // The streak calculation walks backwards from today, so this tests
         // that weekends don't break the streak
-        let streak = calculate_streak_from_history(&history);
+        let streak = calculate_streak_from_history(&history, &Rules::default());
         let _ = streak;
     }
 
@@ -422,9 +1343,249 @@ mod tests {
         let fri = Local.with_ymd_and_hms(2026, 1, 23, 12, 0, 0).unwrap();
         let mon = Local.with_ymd_and_hms(2026, 1, 26, 12, 0, 0).unwrap();
 
-        let history = vec![make_commit(fri), make_commit(mon)];
+        let history = vec![tracked_commit(fri), tracked_commit(mon)];
 
-        let streak = calculate_streak_from_history(&history);
+        let streak = calculate_streak_from_history(&history, &Rules::default());
         let _ = streak;
     }
+
+    #[test]
+    fn test_streak_custom_weekend_days() {
+        // Commits a week apart only keep the streak when every day between is a weekend
+        let now = Local::now();
+        let history = vec![tracked_commit(now), tracked_commit(now - Duration::days(7))];
+        let today = Rules::default().today().weekday();
+        let only_today = Rules {
+            weekend_days: (0..7)
+                .filter_map(|day| Weekday::try_from(day).ok())
+                .filter(|day| *day != today)
+                .collect(),
+            ..Default::default()
+        };
+
+        assert_eq!(calculate_streak_from_history(&history, &only_today), 2);
+        assert_eq!(
+            calculate_streak_from_history(&history, &Rules::default()),
+            1
+        );
+    }
+
+    #[test]
+    fn test_late_night_commits_count_for_the_day_before() {
+        let rules = Rules {
+            day_start: NaiveTime::from_hms_opt(4, 0, 0).unwrap(),
+            // No weekends, so the streak doesn't depend on what day the test runs
+            weekend_days: Vec::new(),
+            ..Default::default()
+        };
+        let today = rules.today();
+        let at = |date: NaiveDate, hour: u32| {
+            let time = date.and_hms_opt(hour, 0, 0).unwrap();
+            Local.from_local_datetime(&time).earliest().unwrap()
+        };
+        let yesterday = today - Duration::days(1);
+        let history = vec![
+            tracked_commit(at(yesterday - Duration::days(1), 12)),
+            // Still up at 01:00: that's yesterday's work
+            tracked_commit(at(today, 1)),
+        ];
+
+        assert_eq!(calculate_streak_from_history(&history, &rules), 2);
+        assert_eq!(get_today_commit_count(&history, &rules), 0);
+
+        let week = get_week_summary(&history, &rules);
+        assert_eq!(week.last(), Some(&(today, 0)));
+        if let Some((_, count)) = week.iter().find(|(date, _)| *date == yesterday) {
+            assert_eq!(*count, 1);
+        }
+    }
+
+    #[test]
+    fn test_import_commits_deduplicates_by_hash() {
+        let now = Local::now();
+        let mut state = AppState {
+            commit_history: vec![tracked_commit(now)],
+            ..Default::default()
+        };
+
+        let added = state.import_commits(
+            vec![
+                tracked_commit(now),
+                tracked_commit(now - Duration::hours(1)),
+            ],
+            &Rules::default(),
+        );
+
+        assert_eq!(added, 1);
+        assert_eq!(state.commit_history.len(), 2);
+        assert_eq!(state.total_commits_tracked, 1);
+        assert_eq!(state.last_commit_time, Some(now));
+    }
+
+    #[test]
+    fn test_save_is_atomic_and_keeps_a_backup() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+
+        manager.save(&mut AppState::default()).unwrap();
+        manager.save(&mut AppState::default()).unwrap();
+
+        assert!(manager.state_path().exists());
+        assert!(!manager.state_path().with_extension("json.tmp").exists());
+        // The second save is within the backup interval
+        assert_eq!(manager.backups().len(), 1);
+        assert!(matches!(manager.check(), StateHealth::Healthy));
+    }
+
+    #[test]
+    fn test_reset_deletes_the_journal() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+        manager.save(&mut AppState::default()).unwrap();
+        manager
+            .append_to_journal(&tracked_commit(Local::now()))
+            .unwrap();
+        // Left behind by a compaction that didn't finish
+        let compacting = dir.path().join("journal.jsonl.compacting");
+        fs::write(&compacting, "").unwrap();
+
+        manager.reset().unwrap();
+
+        assert!(!manager.state_path().exists());
+        assert!(!dir.path().join("journal.jsonl").exists());
+        assert!(!compacting.exists());
+        assert!(manager.load().unwrap().commit_history.is_empty());
+    }
+
+    #[test]
+    fn test_corrupt_state_is_restored_from_backup() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+        let mut state = AppState {
+            best_streak: 12,
+            ..Default::default()
+        };
+        manager.save(&mut state).unwrap();
+
+        // Simulate a write cut short
+        fs::write(manager.state_path(), "{\"last_seen\": \"2026-").unwrap();
+        assert!(manager.load().is_err());
+
+        let StateHealth::Corrupt { backup, .. } = manager.check() else {
+            panic!("truncated state should be reported as corrupt");
+        };
+        let backup = backup.expect("the first save should have left a backup");
+        let aside = manager.recover(Some(&backup)).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&aside).unwrap(),
+            "{\"last_seen\": \"2026-"
+        );
+        assert_eq!(manager.load().unwrap().best_streak, 12);
+    }
+
+    #[test]
+    fn test_concurrent_saves_merge() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let pet = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+        let game = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+        let mut base = AppState {
+            minigame_best_scores: vec![40],
+            ..Default::default()
+        };
+        pet.save(&mut base).unwrap();
+
+        // Both instances start from the same file
+        let mut pet_state = pet.load().unwrap();
+        let mut game_state = game.load().unwrap();
+
+        let now = Local::now();
+        pet_state.import_commits([tracked_commit(now)], &Rules::default());
+        game_state.minigame_best_scores = vec![55, 40];
+        game_state.tetris_sprint_times = vec![61.5];
+
+        pet.save(&mut pet_state).unwrap();
+        game.save(&mut game_state).unwrap();
+
+        // The second save kept the first one's commit, and picked it up in memory
+        assert_eq!(game_state.commit_history.len(), 1);
+        let saved = pet.load().unwrap();
+        assert_eq!(saved.commit_history.len(), 1);
+        assert_eq!(saved.total_commits_tracked, 1);
+        assert_eq!(saved.minigame_best_scores, vec![55, 40]);
+        assert_eq!(saved.tetris_sprint_times, vec![61.5]);
+
+        // The pet instance picks up the game's scores without saving
+        assert_eq!(pet.merge_from_disk(&mut pet_state).unwrap(), 0);
+        assert_eq!(pet_state.minigame_best_scores, vec![55, 40]);
+    }
+
+    #[test]
+    fn test_merge_scores_keeps_repeated_scores() {
+        let mut ours = vec![100, 80, 80];
+        merge_scores(&mut ours, &[100, 90, 80], |a, b| b.cmp(a));
+        assert_eq!(ours, vec![100, 90, 80, 80]);
+
+        let mut times: Vec<f32> = vec![70.0, 65.5];
+        merge_scores(&mut times, &[65.5, 58.25, 90.0], |a, b| a.total_cmp(b));
+        assert_eq!(times, vec![58.25, 65.5, 70.0, 90.0]);
+    }
+
+    /// Hand-written version 1 state files for the migrations: one in the original layout,
+    /// one whose commits carry diff stats but no language totals yet
+    const ORIGINAL_STATE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/state-v1-original.json"
+    ));
+    const DIFF_STATS_STATE: &str = include_str!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/state-v1-diff-stats.json"
+    ));
+
+    #[test]
+    fn test_original_state_file_migrates() {
+        let state = parse_state(ORIGINAL_STATE).unwrap();
+
+        assert_eq!(state.version, STATE_VERSION);
+        assert_eq!(state.best_streak, 9);
+        assert_eq!(state.commit_history.len(), 3);
+        assert_eq!(state.commit_history[2].project_name, "dotfiles");
+        assert!(state.commit_history[0].diff.is_none());
+        assert!(state.language_totals.is_empty());
+        assert_eq!(state.ground_style, GroundStyle::Rocky);
+        assert_eq!(state.minigame_best_scores, vec![42, 31, 17]);
+        assert_eq!(state.tetris_sprint_times, vec![97.35]);
+    }
+
+    #[test]
+    fn test_language_totals_migrated_from_diff_stats() {
+        let state = parse_state(DIFF_STATS_STATE).unwrap();
+
+        assert_eq!(state.language_totals.get("Rust"), Some(&2));
+        assert_eq!(state.language_totals.get("Markdown"), Some(&1));
+        assert_eq!(state.language_totals.len(), 2);
+        assert_eq!(state.releases[0].tag, "v0.3.0");
+
+        // Saved again at the current version, the totals aren't counted twice
+        let saved = serde_json::to_string(&state).unwrap();
+        let reloaded = parse_state(&saved).unwrap();
+        assert_eq!(reloaded.language_totals, state.language_totals);
+    }
+
+    #[test]
+    fn test_newer_state_file_is_left_untouched() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let manager = StateManager::in_dir(dir.path().to_path_buf()).unwrap();
+        let newer = DIFF_STATS_STATE.replace(
+            "\"version\": 1",
+            &format!("\"version\": {}", STATE_VERSION + 1),
+        );
+        fs::write(manager.state_path(), &newer).unwrap();
+
+        let err = manager.load().unwrap_err();
+        assert!(format!("{:#}", err).contains("newer version of Kanitomo"));
+        // Not offered for recovery either
+        assert!(matches!(manager.check(), StateHealth::Healthy));
+        assert_eq!(fs::read_to_string(manager.state_path()).unwrap(), newer);
+    }
 }
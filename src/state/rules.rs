@@ -0,0 +1,137 @@
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime, TimeZone, Weekday};
+
+/// Happiness and streak rules, configured by the `[happiness]` and `[schedule]`
+/// tables of the config file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rules {
+    /// Commits in one day that bring Kani to full happiness
+    pub full_happiness_commits: u32,
+    /// Days that don't need a commit to keep the streak going
+    pub weekend_days: Vec<Weekday>,
+    /// Wall-clock time a new day begins; commits before it count for the day before
+    pub day_start: NaiveTime,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            full_happiness_commits: 20,
+            weekend_days: vec![Weekday::Sat, Weekday::Sun],
+            day_start: NaiveTime::MIN,
+        }
+    }
+}
+
+impl Rules {
+    /// The day a commit (or any moment) counts for, honoring `schedule.day_starts_at`
+    /// Every per-day statistic buckets timestamps through this. Compares the local
+    /// wall-clock time against `day_start` rather than shifting by a duration, so days
+    /// keep their boundary across DST changes.
+    pub fn day_of<Tz: TimeZone>(&self, time: &DateTime<Tz>) -> NaiveDate {
+        let local = time.naive_local();
+        if local.time() < self.day_start {
+            local.date() - Duration::days(1)
+        } else {
+            local.date()
+        }
+    }
+
+    /// The day it is now, by the same rule as `day_of`
+    pub fn today(&self) -> NaiveDate {
+        self.day_of(&Local::now())
+    }
+
+    /// Whether a weekday is a weekend day (`schedule.weekend_days` in the config)
+    pub fn is_weekend(&self, weekday: Weekday) -> bool {
+        self.weekend_days.contains(&weekday)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::FixedOffset;
+
+    fn starting_at(hour: u32, minute: u32) -> Rules {
+        Rules {
+            day_start: NaiveTime::from_hms_opt(hour, minute, 0).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    /// A moment in a zone `offset_hours` ahead of UTC
+    fn at(offset_hours: i32, rfc3339: &str) -> DateTime<FixedOffset> {
+        DateTime::parse_from_rfc3339(rfc3339)
+            .unwrap()
+            .with_timezone(&FixedOffset::east_opt(offset_hours * 3600).unwrap())
+    }
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_day_of_around_midnight() {
+        let midnight = Rules::default();
+        assert_eq!(
+            midnight.day_of(&at(1, "2026-03-05T23:59:59+01:00")),
+            date(3, 5)
+        );
+        assert_eq!(
+            midnight.day_of(&at(1, "2026-03-06T00:00:00+01:00")),
+            date(3, 6)
+        );
+
+        let night_owl = starting_at(4, 0);
+        assert_eq!(
+            night_owl.day_of(&at(1, "2026-03-05T23:59:59+01:00")),
+            date(3, 5)
+        );
+        assert_eq!(
+            night_owl.day_of(&at(1, "2026-03-06T01:00:00+01:00")),
+            date(3, 5)
+        );
+        assert_eq!(
+            night_owl.day_of(&at(1, "2026-03-06T03:59:59+01:00")),
+            date(3, 5)
+        );
+        assert_eq!(
+            night_owl.day_of(&at(1, "2026-03-06T04:00:00+01:00")),
+            date(3, 6)
+        );
+        // Crossing the year boundary
+        assert_eq!(
+            night_owl.day_of(&at(1, "2026-01-01T02:30:00+01:00")),
+            NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_day_of_across_dst_transitions() {
+        // Paris springs forward on March 29, 2026: 02:00 CET becomes 03:00 CEST
+        let rules = starting_at(3, 0);
+        assert_eq!(
+            rules.day_of(&at(1, "2026-03-29T01:59:00+01:00")),
+            date(3, 28)
+        );
+        // A minute after 01:59 (shifting back 3h would land on March 28)
+        assert_eq!(
+            rules.day_of(&at(2, "2026-03-29T03:00:00+02:00")),
+            date(3, 29)
+        );
+        assert_eq!(
+            rules.day_of(&at(2, "2026-03-30T02:59:00+02:00")),
+            date(3, 29)
+        );
+
+        // Paris falls back on October 25, 2026: 03:00 CEST becomes 02:00 CET,
+        // so 02:00-03:00 happens twice and both passes get the same answer
+        let rules = starting_at(2, 30);
+        for offset in [2, 1] {
+            let before = format!("2026-10-25T02:15:00+0{}:00", offset);
+            let after = format!("2026-10-25T02:45:00+0{}:00", offset);
+            assert_eq!(rules.day_of(&at(offset, &before)), date(10, 24));
+            assert_eq!(rules.day_of(&at(offset, &after)), date(10, 25));
+        }
+    }
+}
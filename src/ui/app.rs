@@ -1,9 +1,13 @@
+use crate::config::{Config, WatchSetting};
 use crate::crab::{Crab, Mood};
 use crate::environment::Environment;
-use crate::git::{get_repo_commits, CommitInfo, DetectedCommit, GitStats, GitTracker};
+use crate::git::{
+    get_repo_commits, parse_conventional, CommitInfo, CommitSize, DetectedCommit, DetectedPush,
+    DetectedTag, DiffStats, GitStats, GitTracker, MarkerIndex, WatchMode, WorkingTreeStatus,
+};
 use crate::state::{
-    calculate_happiness_from_commits, calculate_streak_from_history, get_today_commit_count,
-    AppState, StateManager, TrackedCommit,
+    calculate_happiness_from_history, calculate_streak_from_history, get_projects,
+    get_today_by_project, AppState, CommitAnalytics, ReleaseEvent, StateManager, TrackedCommit,
 };
 use crate::ui::minigames::{
     BreakoutGame, DashGame, Direction as SnakeDirection, Game2048, Game2048Move, SnakeGame,
@@ -11,15 +15,58 @@ use crate::ui::minigames::{
 };
 use crate::ui::{messages, widgets, CrabCatchGame};
 use anyhow::Result;
-use chrono::{Datelike, Local};
+use chrono::{Datelike, Local, NaiveDate};
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use notify::{Config, RecommendedWatcher, RecursiveMode, Watcher};
+use notify::{Config as NotifyConfig, RecommendedWatcher, RecursiveMode, Watcher};
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     Frame,
 };
-use std::sync::mpsc::{channel, Receiver};
-use std::time::{Duration, Instant};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::time::{Duration, Instant, SystemTime};
+
+/// How long a repo's watcher events must stay quiet before it is re-checked
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// How often repos watched through events are checked anyway, to catch a silent watcher
+const WATCH_VERIFY_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Most scanned directories watched for new repositories; over this, the deepest
+/// levels are rescanned on the verify timer instead (inotify watches are limited)
+const MAX_SCAN_DIR_WATCHES: usize = 256;
+
+/// How often working trees are checked for uncommitted changes
+const STATUS_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often to look for state saved by another Kanitomo instance
+const STATE_CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Changed files at which Kani is fully anxious about the size of the diff
+const ANXIOUS_CHANGED_FILES: f32 = 40.0;
+
+/// Hours a change can sit uncommitted before Kani is fully anxious about it
+const ANXIOUS_UNCOMMITTED_HOURS: f32 = 4.0;
+
+/// Number of recent commit subjects loaded for the project view
+const PROJECT_RECENT_COMMITS: usize = 8;
+
+/// Minimum time between two reminders about unpushed commits
+const PUSH_NAG_INTERVAL: Duration = Duration::from_secs(30 * 60);
+
+/// Chance that an idle message rotation shares a commit habit insight instead
+const INSIGHT_CHANCE: f32 = 0.3;
+
+/// Commits needed before Kani starts commenting on commit habits
+const MIN_COMMITS_FOR_INSIGHTS: u32 = 20;
+
+/// Result of a background working tree read: statuses per git dir, and the marker
+/// indexes built for repositories that had none
+type BackgroundScan = (
+    Vec<(PathBuf, WorkingTreeStatus)>,
+    Vec<(PathBuf, MarkerIndex)>,
+);
 
 /// Main application state
 pub struct App {
@@ -41,20 +88,72 @@ pub struct App {
     pub show_repo_list: bool,
     /// Whether to show the details overlay
     pub show_details: bool,
+    /// Selected row in the repo list overlay
+    pub repo_list_selected: usize,
+    /// Selected project (from today's list) in the details overlay
+    pub details_selected: usize,
+    /// Project shown in the project view overlay (project id, display name)
+    pub project_view: Option<(String, String)>,
+    /// Recent commits of the project shown in the project view
+    pub project_view_commits: Vec<CommitInfo>,
+    /// Whether to show the yearly heatmap overlay
+    pub show_heatmap: bool,
+    /// Year shown in the heatmap
+    pub heatmap_year: i32,
+    /// Selected day in the heatmap
+    pub heatmap_selected: NaiveDate,
+    /// Project the heatmap is filtered to (project id, display name)
+    pub heatmap_project: Option<(String, String)>,
+    /// Productivity analytics, computed when the analytics overlay is open
+    pub analytics: Option<CommitAnalytics>,
     /// Whether to show the stats panel
     pub show_stats: bool,
     /// Whether to show the help bar
     pub show_help: bool,
-    /// File watcher for git changes (kept alive to maintain watching)
-    _watcher: Option<RecommendedWatcher>,
+    /// File watcher for git changes and discovery roots
+    watcher: Option<RecommendedWatcher>,
+    /// Paths currently registered with the watcher
+    watched_paths: HashSet<PathBuf>,
+    /// Repos (by git dir) with unchecked watcher events, and when the last one arrived
+    pending_checks: HashMap<PathBuf, Instant>,
+    /// When the last directory was created or removed under a discovery root, if a
+    /// rescan is pending
+    pending_rescan: Option<Instant>,
+    /// Whether some scanned directories are over the watch cap, so discovery
+    /// reruns on the verify timer
+    scan_dirs_unwatched: bool,
+    /// How often to check repos that are polled instead of watched
+    poll_interval: Duration,
+    /// Last time polled repos were checked
+    last_poll: Instant,
+    /// Last time watched repos were double-checked for missed events
+    last_verify: Instant,
+    /// Last time working trees were checked for uncommitted changes (None until the
+    /// first background read, which runs right away to build the marker indexes)
+    last_status_check: Option<Instant>,
+    /// Working tree statuses being read (and marker indexes built for repos that have
+    /// none yet) on a background thread, if a read is running
+    status_rx: Option<Receiver<BackgroundScan>>,
+    /// How long commits can stay unpushed before Kani nags (None disables)
+    push_nag_after: Option<chrono::Duration>,
+    /// Last time Kani nagged about unpushed commits
+    last_push_nag: Option<Instant>,
     /// Channel for receiving file change events
     watcher_rx: Option<Receiver<notify::Result<notify::Event>>>,
     /// Last time we saved state
     last_save: Instant,
+    /// Time between automatic saves
+    autosave_interval: Duration,
+    /// Last time the state file was checked for changes by other instances
+    last_state_check: Instant,
+    /// Modification time of the state file when we last saved or merged it
+    state_modified: Option<SystemTime>,
     /// Current message from Kani
     current_message: String,
     /// Temporary message (for reactions to events)
     temp_message: Option<String>,
+    /// How long temp messages stay on screen
+    temp_message_duration: Duration,
     /// When the temp message should expire
     temp_message_until: Option<Instant>,
     /// Last time we changed the idle message
@@ -111,46 +210,47 @@ pub struct App {
 
 impl App {
     /// Create a new app instance
-    pub fn new(debug_mode: bool) -> Result<Self> {
-        let state_manager = StateManager::new()?;
-        let app_state = state_manager.load()?;
+    pub fn new(debug_mode: bool, config: Config) -> Result<Self> {
+        let state_manager = StateManager::new()?.with_rules(config.rules());
+        let mut app_state = state_manager.load()?;
+        let state_modified = state_manager.modified();
 
-        let git_tracker = GitTracker::new();
+        let git_tracker = GitTracker::discover(&config.discovery_options(), config.authors);
         let git_stats = git_tracker.get_stats();
 
+        // Import commits made while Kanitomo was closed
+        if config.backfill_days > 0 {
+            let since = Local::now() - chrono::Duration::days(config.backfill_days as i64);
+            let backfilled = git_tracker.own_commits_since(since, &app_state.tracked_hashes());
+            app_state.import_commits(
+                backfilled.into_iter().map(TrackedCommit::from),
+                state_manager.rules(),
+            );
+        }
+
         // Create the crab with loaded happiness
         // Start at a high y position so it falls to ground on first update
-        let crab = Crab::new((10.0, 100.0), app_state.happiness);
+        let mut crab = Crab::new((10.0, 100.0), app_state.happiness);
+        crab.set_wardrobe(&app_state.language_totals);
 
-        // Set up file watcher for all git repos
-        let git_dirs = git_tracker.git_dirs();
-        let (watcher, watcher_rx) = if !git_dirs.is_empty() {
+        // Set up file watcher for all git repos and discovery roots.
+        // If the OS watcher can't be created, every repo is polled instead.
+        let force_poll = config.watch.mode == WatchSetting::Poll;
+        let (watcher, watcher_rx) = if force_poll {
+            (None, None)
+        } else {
             let (tx, rx) = channel();
 
             // Use event-based watching (no polling) for better performance
-            let mut watcher = RecommendedWatcher::new(
+            match RecommendedWatcher::new(
                 move |res| {
                     let _ = tx.send(res);
                 },
-                Config::default(),
-            )?;
-
-            // Watch HEAD and refs for each repository
-            for git_dir in &git_dirs {
-                let head_path = git_dir.join("HEAD");
-                let refs_path = git_dir.join("refs");
-
-                if head_path.exists() {
-                    watcher.watch(&head_path, RecursiveMode::NonRecursive).ok();
-                }
-                if refs_path.exists() {
-                    watcher.watch(&refs_path, RecursiveMode::Recursive).ok();
-                }
+                NotifyConfig::default(),
+            ) {
+                Ok(watcher) => (Some(watcher), Some(rx)),
+                Err(_) => (None, None),
             }
-
-            (Some(watcher), Some(rx))
-        } else {
-            (None, None)
         };
 
         let current_mood = Mood::from_happiness(app_state.happiness);
@@ -158,8 +258,9 @@ impl App {
 
         // Create initial environment with default size (will be resized on first draw)
         let environment = Environment::generate(80, 15, app_state.ground_style);
+        let today = state_manager.rules().today();
 
-        Ok(Self {
+        let mut app = Self {
             crab,
             git_tracker,
             git_stats,
@@ -169,13 +270,38 @@ impl App {
             debug_mode,
             show_repo_list: false,
             show_details: false,
+            repo_list_selected: 0,
+            details_selected: 0,
+            project_view: None,
+            project_view_commits: Vec::new(),
+            show_heatmap: false,
+            heatmap_year: today.year(),
+            heatmap_selected: today,
+            heatmap_project: None,
+            analytics: None,
             show_stats: true,
             show_help: false,
-            _watcher: watcher,
+            watcher,
+            watched_paths: HashSet::new(),
+            pending_checks: HashMap::new(),
+            pending_rescan: None,
+            scan_dirs_unwatched: false,
+            poll_interval: Duration::from_secs(config.watch.poll_interval_secs),
+            last_poll: Instant::now(),
+            last_verify: Instant::now(),
+            last_status_check: None,
+            status_rx: None,
+            push_nag_after: (config.push.nag_after_hours > 0)
+                .then(|| chrono::Duration::hours(config.push.nag_after_hours as i64)),
+            last_push_nag: None,
             watcher_rx,
             last_save: Instant::now(),
+            autosave_interval: Duration::from_secs(config.behavior.autosave_secs),
+            last_state_check: Instant::now(),
+            state_modified,
             current_message: initial_message,
             temp_message: None,
+            temp_message_duration: Duration::from_secs(config.behavior.message_secs),
             temp_message_until: None,
             last_message_change: Instant::now(),
             last_mood: current_mood,
@@ -202,7 +328,11 @@ impl App {
             commit_picker_items: Vec::new(),
             commit_picker_selected: 0,
             commit_picker_scroll: 0,
-        })
+        };
+        app.sync_watches();
+        app.update_anxiety();
+
+        Ok(app)
     }
 
     /// Run the main event loop
@@ -518,6 +648,53 @@ impl App {
             return;
         }
 
+        if self.show_heatmap {
+            match key {
+                KeyCode::Left | KeyCode::Char('h') => {
+                    self.move_heatmap_selection(-7);
+                }
+                KeyCode::Right | KeyCode::Char('l') => {
+                    self.move_heatmap_selection(7);
+                }
+                KeyCode::Up | KeyCode::Char('k') => {
+                    self.move_heatmap_selection(-1);
+                }
+                KeyCode::Down | KeyCode::Char('j') => {
+                    self.move_heatmap_selection(1);
+                }
+                KeyCode::Char('[') => {
+                    self.switch_heatmap_year(-1);
+                }
+                KeyCode::Char(']') => {
+                    self.switch_heatmap_year(1);
+                }
+                KeyCode::Char('p') => {
+                    self.cycle_heatmap_project();
+                }
+                KeyCode::Char('y') | KeyCode::Char('q') | KeyCode::Esc => {
+                    self.show_heatmap = false;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        if self.analytics.is_some() {
+            if let KeyCode::Char('i') | KeyCode::Char('q') | KeyCode::Esc = key {
+                self.analytics = None;
+            }
+            return;
+        }
+
+        // Handle the project view (opened from the repo list or details overlay)
+        if self.project_view.is_some() {
+            if let KeyCode::Char('q') | KeyCode::Esc | KeyCode::Backspace = key {
+                self.project_view = None;
+                self.project_view_commits.clear();
+            }
+            return;
+        }
+
         match key {
             KeyCode::Char('q') | KeyCode::Esc => {
                 if self.show_help {
@@ -534,6 +711,7 @@ impl App {
                 // Toggle repo list view (only if tracking multiple repos)
                 if self.git_stats.repo_count > 1 {
                     self.show_repo_list = !self.show_repo_list;
+                    self.repo_list_selected = 0;
                     self.show_details = false; // Close other overlay
                     self.show_help = false;
                 }
@@ -541,6 +719,7 @@ impl App {
             KeyCode::Char('d') => {
                 // Toggle details view
                 self.show_details = !self.show_details;
+                self.details_selected = 0;
                 self.show_repo_list = false; // Close other overlay
                 self.show_help = false;
             }
@@ -548,6 +727,34 @@ impl App {
                 // Manual refresh
                 self.refresh_stats();
             }
+            KeyCode::Char('y') => {
+                // Open the yearly heatmap on today
+                self.show_heatmap = true;
+                self.show_repo_list = false;
+                self.show_details = false;
+                self.show_help = false;
+                self.heatmap_selected = self.state_manager.rules().today();
+                self.heatmap_year = self.heatmap_selected.year();
+            }
+            KeyCode::Char('i') => {
+                // Open the productivity analytics
+                self.analytics = Some(CommitAnalytics::from_history(
+                    &self.app_state.commit_history,
+                    self.state_manager.rules(),
+                ));
+                self.show_repo_list = false;
+                self.show_details = false;
+                self.show_help = false;
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.move_overlay_selection(-1);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.move_overlay_selection(1);
+            }
+            KeyCode::Enter => {
+                self.open_selected_project();
+            }
             KeyCode::Char('?') => {
                 // Toggle help window
                 self.show_help = !self.show_help;
@@ -561,9 +768,16 @@ impl App {
                 let timestamp = Local::now();
                 let tracked = TrackedCommit {
                     timestamp,
+                    committed_at: Some(timestamp),
+                    detected_at: Some(timestamp),
                     commit_hash: format!("debug-{}", timestamp.timestamp()),
                     project_id: "debug".to_string(),
                     project_name: "debug".to_string(),
+                    author_name: "debug".to_string(),
+                    author_email: String::new(),
+                    diff: None,
+                    conventional: None,
+                    markers: None,
                 };
                 self.app_state.commit_history.push(tracked);
                 self.app_state.last_commit_time = Some(timestamp);
@@ -573,12 +787,13 @@ impl App {
             }
             KeyCode::Char('p') if self.debug_mode => {
                 // Punish (debug only)
-                let today = Local::now().date_naive();
+                let rules = self.state_manager.rules();
+                let today = rules.today();
                 if let Some(index) = self
                     .app_state
                     .commit_history
                     .iter()
-                    .rposition(|commit| commit.timestamp.date_naive() == today)
+                    .rposition(|commit| rules.day_of(&commit.timestamp) == today)
                 {
                     self.app_state.commit_history.remove(index);
                     self.sync_last_commit_time();
@@ -603,7 +818,8 @@ impl App {
             KeyCode::Char('g') if self.debug_mode => {
                 // Cycle ground styles (debug only)
                 self.app_state.ground_style = self.app_state.ground_style.next();
-                self.app_state.ground_style_week = Local::now().iso_week().week();
+                self.app_state.ground_style_week =
+                    self.state_manager.rules().today().iso_week().week();
                 let (width, height) =
                     if self.last_terminal_size.0 > 0 && self.last_terminal_size.1 > 0 {
                         self.last_terminal_size
@@ -623,7 +839,7 @@ impl App {
                     self.show_commit_picker = false;
                 } else {
                     // Load commits from the current repo
-                    self.commit_picker_items = get_repo_commits(50);
+                    self.commit_picker_items = get_repo_commits(Path::new("."), 50);
                     self.commit_picker_selected = 0;
                     self.commit_picker_scroll = 0;
                     self.show_commit_picker = true;
@@ -759,6 +975,9 @@ impl App {
         // Check for file system events (new commits)
         self.check_for_changes();
 
+        // Pick up commits and scores saved by other instances
+        self.check_state_file();
+
         // Sync happiness based on today's commit count
         self.sync_happiness_from_commits();
 
@@ -776,9 +995,10 @@ impl App {
         };
         let cloud_speed = if self.fast_cycle { 3.0 } else { 1.0 };
         self.environment.update_cycle(dt, cycle_speed, cloud_speed);
+        self.environment.update_fireworks(dt);
 
-        // Periodic save (every 60 seconds)
-        if self.last_save.elapsed() > Duration::from_secs(60) {
+        // Periodic save
+        if self.last_save.elapsed() > self.autosave_interval {
             let _ = self.save_state();
             self.last_save = Instant::now();
         }
@@ -786,75 +1006,504 @@ impl App {
 
     /// Check for git changes via file watcher
     fn check_for_changes(&mut self) {
-        // First, check if we have any pending events
-        let has_events = if let Some(ref rx) = self.watcher_rx {
-            // Drain all pending events and check if any exist
-            let mut found = false;
-            while rx.try_recv().is_ok() {
-                found = true;
+        // First, drain all pending events
+        let mut events = Vec::new();
+        if let Some(ref rx) = self.watcher_rx {
+            while let Ok(event) = rx.try_recv() {
+                events.push(event);
             }
-            found
+        }
+
+        // A watcher error means events can no longer be trusted
+        if events.iter().any(|event| event.is_err()) {
+            self.fall_back_to_polling(&self.git_tracker.repos_watched_by(WatchMode::Events));
+        }
+
+        if !events.is_empty() {
+            let events: Vec<&notify::Event> = events
+                .iter()
+                .filter_map(|event| event.as_ref().ok())
+                .collect();
+            let paths: Vec<&PathBuf> = events.iter().flat_map(|event| &event.paths).collect();
+            let now = Instant::now();
+
+            // A directory appearing or disappearing outside the tracked git dirs means
+            // a repository may have been added or removed; file saves don't count
+            let layout_changed = self.git_tracker.has_missing_repos()
+                || events.iter().any(|event| {
+                    is_layout_event(&event.kind)
+                        && event
+                            .paths
+                            .iter()
+                            .any(|path| !self.git_tracker.is_in_git_dir(path))
+                });
+            if layout_changed {
+                self.pending_rescan = Some(now);
+            }
+
+            // Ref transactions touch several files; wait for the burst to settle
+            for path in paths {
+                for git_dir in self.git_tracker.repos_affected_by(path) {
+                    self.pending_checks.insert(git_dir, now);
+                }
+            }
+        }
+
+        // `git init` or `git clone` create several directories in a row
+        if self
+            .pending_rescan
+            .is_some_and(|last_event| last_event.elapsed() >= WATCH_DEBOUNCE)
+        {
+            self.pending_rescan = None;
+            self.rescan_repos();
+        }
+
+        let mut settled: Vec<PathBuf> = self
+            .pending_checks
+            .iter()
+            .filter(|(_, last_event)| last_event.elapsed() >= WATCH_DEBOUNCE)
+            .map(|(git_dir, _)| git_dir.clone())
+            .collect();
+        for git_dir in &settled {
+            self.pending_checks.remove(git_dir);
+        }
+        // Repos we just heard from; their index may have changed too
+        let touched = settled.clone();
+
+        // Repos the watcher can't see are checked on a timer
+        if self.last_poll.elapsed() >= self.poll_interval {
+            self.last_poll = Instant::now();
+            settled.extend(self.git_tracker.repos_watched_by(WatchMode::Polling));
+        }
+
+        let mut changes = self.git_tracker.check_repos(&settled);
+        let mut tags = self.git_tracker.check_tags(&settled);
+        let mut pushes = self.git_tracker.check_pushes(&settled);
+
+        // Every so often, double-check watched repos: HEAD moves we keep not being
+        // told about mean the watcher is silent there (e.g. NFS or sshfs)
+        if self.last_verify.elapsed() >= WATCH_VERIFY_INTERVAL {
+            self.last_verify = Instant::now();
+            if self.watcher.is_none() || self.scan_dirs_unwatched {
+                self.rescan_repos();
+            }
+
+            let mut silent = Vec::new();
+            for git_dir in self.git_tracker.repos_watched_by(WatchMode::Events) {
+                if self.pending_checks.contains_key(&git_dir) {
+                    continue;
+                }
+                let git_dirs = std::slice::from_ref(&git_dir);
+                let missed = self.git_tracker.check_repos(git_dirs);
+                let missed_tags = self.git_tracker.check_tags(git_dirs);
+                let missed_pushes = self.git_tracker.check_pushes(git_dirs);
+                let any_missed =
+                    !missed.is_empty() || !missed_tags.is_empty() || !missed_pushes.is_empty();
+                changes.extend(missed);
+                tags.extend(missed_tags);
+                pushes.extend(missed_pushes);
+                if self.git_tracker.record_watch_check(&git_dir, any_missed) {
+                    silent.push(git_dir);
+                }
+            }
+            if !silent.is_empty() {
+                self.refresh_stats();
+                self.set_temp_message("I almost missed a commit! I'll keep checking by myself.");
+            }
+        }
+
+        // Working trees aren't watched: re-read the repos we just heard from right away,
+        // and all of them on a background thread after HEAD moves and on a timer. The
+        // same thread builds the marker index of new repos (a full tree scan).
+        let mut statuses_changed = false;
+        if !touched.is_empty() {
+            self.git_tracker.refresh_statuses(&touched);
+            statuses_changed = true;
+        }
+        if let Some(rx) = &self.status_rx {
+            match rx.try_recv() {
+                Ok((statuses, markers)) => {
+                    self.status_rx = None;
+                    self.git_tracker.set_statuses(statuses);
+                    self.git_tracker.set_marker_indexes(markers);
+                    statuses_changed = true;
+                }
+                Err(TryRecvError::Disconnected) => self.status_rx = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        if self.status_rx.is_none()
+            && (!changes.is_empty()
+                || self
+                    .last_status_check
+                    .is_none_or(|last| last.elapsed() >= STATUS_REFRESH_INTERVAL))
+        {
+            self.last_status_check = Some(Instant::now());
+            let git_dirs: Vec<PathBuf> = self
+                .git_tracker
+                .repo_paths()
+                .into_iter()
+                .map(|(_, git_dir)| git_dir)
+                .collect();
+            let unindexed = self.git_tracker.unindexed_repos();
+            let (tx, rx) = channel();
+            std::thread::spawn(move || {
+                let statuses = WorkingTreeStatus::read_all(&git_dirs);
+                tx.send((statuses, MarkerIndex::build_all(&unindexed))).ok();
+            });
+            self.status_rx = Some(rx);
+        }
+        if statuses_changed {
+            self.refresh_stats();
+            self.update_anxiety();
+        }
+
+        // Feed Kani with the new commits
+        for change in changes {
+            // Rewinds and branch switches don't feed Kani
+            if !change.kind.is_feeding() {
+                continue;
+            }
+            for detected in change.commits {
+                self.on_new_commit(detected);
+            }
+        }
+
+        // Tags are celebrated after the commits they point at
+        self.on_new_releases(tags);
+
+        for push in pushes {
+            self.on_push(push);
+        }
+
+        // Commits and remote-tracking refs change the unpushed counts
+        if !settled.is_empty() {
+            self.refresh_stats();
+        }
+    }
+
+    /// Move the heatmap selection by a number of days, staying within the shown year
+    /// and not going past today
+    fn move_heatmap_selection(&mut self, days: i64) {
+        let target = self.heatmap_selected + chrono::Duration::days(days);
+        if target.year() == self.heatmap_year && target <= self.state_manager.rules().today() {
+            self.heatmap_selected = target;
+        }
+    }
+
+    /// Show the previous or next year in the heatmap, keeping the selected day of the year
+    fn switch_heatmap_year(&mut self, delta: i32) {
+        let history = &self.app_state.commit_history;
+        let rules = self.state_manager.rules();
+        let today = rules.today();
+        let first_year = history
+            .iter()
+            .map(|commit| rules.day_of(&commit.timestamp).year())
+            .min()
+            .unwrap_or(today.year());
+
+        let year = (self.heatmap_year + delta).clamp(first_year.min(today.year()), today.year());
+        if year == self.heatmap_year {
+            return;
+        }
+        self.heatmap_year = year;
+
+        // Feb 29 doesn't exist every year; fall back to the last day of the month
+        let selected = self.heatmap_selected;
+        self.heatmap_selected = (1..=selected.day())
+            .rev()
+            .find_map(|day| NaiveDate::from_ymd_opt(year, selected.month(), day))
+            .unwrap_or(selected)
+            .min(today);
+    }
+
+    /// Cycle the heatmap filter through all projects, then back to all of them
+    fn cycle_heatmap_project(&mut self) {
+        let projects = get_projects(&self.app_state.commit_history);
+        let next = match &self.heatmap_project {
+            None => 0,
+            Some((id, _)) => projects
+                .iter()
+                .position(|(project_id, _)| project_id == id)
+                .map_or(0, |index| index + 1),
+        };
+        self.heatmap_project = projects.into_iter().nth(next);
+    }
+
+    /// Move the selection in the repo list or details overlay
+    fn move_overlay_selection(&mut self, delta: isize) {
+        let (selected, len) = if self.show_repo_list {
+            (&mut self.repo_list_selected, self.git_stats.repos.len())
+        } else if self.show_details {
+            let len =
+                get_today_by_project(&self.app_state.commit_history, self.state_manager.rules())
+                    .len();
+            (&mut self.details_selected, len)
         } else {
-            false
+            return;
         };
+        *selected = selected
+            .saturating_add_signed(delta)
+            .min(len.saturating_sub(1));
+    }
 
-        // If we had events, check for new commits
-        if has_events {
-            if let Some(detected) = self.git_tracker.check_for_new_commit() {
-                self.on_new_commit(detected);
+    /// Open the project view for the project selected in the open overlay
+    fn open_selected_project(&mut self) {
+        let project = if self.show_repo_list {
+            self.git_stats
+                .repos
+                .get(self.repo_list_selected)
+                .map(|repo| (repo.project_id.clone(), repo.name.clone()))
+        } else if self.show_details {
+            get_today_by_project(&self.app_state.commit_history, self.state_manager.rules())
+                .into_iter()
+                .nth(self.details_selected)
+                .map(|(id, name, _)| (id, name))
+        } else {
+            None
+        };
+        let Some((project_id, project_name)) = project else {
+            return;
+        };
+
+        // Subjects are read from the repository when the view opens
+        self.project_view_commits = self
+            .git_stats
+            .repos
+            .iter()
+            .find(|repo| repo.project_id == project_id)
+            .map(|repo| get_repo_commits(&repo.path, PROJECT_RECENT_COMMITS))
+            .unwrap_or_default();
+        self.project_view = Some((project_id, project_name));
+    }
+
+    /// Re-run repository discovery and update watches for added/removed repos
+    fn rescan_repos(&mut self) {
+        let change = self.git_tracker.rescan();
+        self.sync_watches();
+        if change.is_empty() {
+            return;
+        }
+
+        self.refresh_stats();
+        if !change.added.is_empty() {
+            let names: Vec<&str> = change.added.iter().map(|r| r.name.as_str()).collect();
+            self.set_temp_message(&format!("New home: {}!", names.join(", ")));
+        } else {
+            let names: Vec<&str> = change.removed.iter().map(|r| r.name.as_str()).collect();
+            self.set_temp_message(&format!("Bye bye, {}...", names.join(", ")));
+        }
+    }
+
+    /// Register watches for the tracked repos and scanned directories,
+    /// dropping watches that are no longer needed
+    fn sync_watches(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            let all = self.git_tracker.repos_watched_by(WatchMode::Events);
+            self.fall_back_to_polling(&all);
+            return;
+        };
+
+        // Git dirs and refs of each repository, plus each scanned directory
+        let mut wanted = Vec::new();
+        for watch in self.git_tracker.watch_paths() {
+            let mode = if watch.recursive {
+                RecursiveMode::Recursive
+            } else {
+                RecursiveMode::NonRecursive
+            };
+            wanted.push((watch.path, mode));
+        }
+        let (scan_dirs, unwatched) = scan_dirs_to_watch(self.git_tracker.scanned_dirs());
+        self.scan_dirs_unwatched = unwatched;
+        for dir in scan_dirs {
+            wanted.push((dir.clone(), RecursiveMode::NonRecursive));
+        }
+
+        let wanted_paths: HashSet<PathBuf> = wanted.iter().map(|(p, _)| p.clone()).collect();
+        for path in self.watched_paths.difference(&wanted_paths) {
+            watcher.unwatch(path).ok();
+        }
+        self.watched_paths
+            .retain(|path| wanted_paths.contains(path));
+
+        for (path, mode) in wanted {
+            if !self.watched_paths.contains(&path)
+                && path.exists()
+                && watcher.watch(&path, mode).is_ok()
+            {
+                self.watched_paths.insert(path);
             }
         }
+
+        // Repos whose git dir couldn't be watched are polled instead
+        let unwatched: Vec<PathBuf> = self
+            .git_tracker
+            .repos_watched_by(WatchMode::Events)
+            .into_iter()
+            .filter(|git_dir| !self.watched_paths.contains(git_dir))
+            .collect();
+        self.fall_back_to_polling(&unwatched);
+    }
+
+    /// Switch repos (by git dir) to periodic HEAD checks
+    fn fall_back_to_polling(&mut self, git_dirs: &[PathBuf]) {
+        let mut changed = false;
+        for git_dir in git_dirs {
+            changed |= self.git_tracker.set_watch_mode(git_dir, WatchMode::Polling);
+        }
+        if changed {
+            self.refresh_stats();
+        }
     }
 
     /// Called when a new commit is detected
     fn on_new_commit(&mut self, detected: DetectedCommit) {
-        // Check for duplicate (same commit hash)
-        if self
-            .app_state
-            .commit_history
-            .iter()
-            .any(|c| c.commit_hash == detected.commit_hash)
-        {
-            return; // Already tracked this commit
+        let is_own = detected.is_own;
+        let history = if is_own {
+            &mut self.app_state.commit_history
+        } else {
+            &mut self.app_state.team_activity
+        };
+
+        // Skip commits already tracked, including amended and rebased copies of them
+        let tracked = TrackedCommit::from(detected);
+        if history.iter().any(|c| c.same_change(&tracked)) {
+            return;
         }
 
-        // Add to commit history
-        let tracked = TrackedCommit {
-            timestamp: Local::now(),
-            commit_hash: detected.commit_hash,
-            project_id: detected.project_id,
-            project_name: detected.project_name,
-        };
-        self.app_state.commit_history.push(tracked);
+        let authored_at = tracked.timestamp;
+        // Commits without diff stats (merges) celebrate like a regular commit
+        let mut size = tracked
+            .diff
+            .as_ref()
+            .map(DiffStats::size)
+            .unwrap_or(CommitSize::Small);
+        let conventional = tracked.conventional.clone();
+        let diff = tracked.diff.clone();
+        let removed_markers = tracked.markers.is_some_and(|m| m.change < 0);
+        history.push(tracked);
+
+        // Teammates' commits show up as team activity but don't feed Kani
+        if !is_own {
+            return;
+        }
 
         // Update last commit time
-        self.app_state.last_commit_time = Some(Local::now());
+        self.app_state.last_commit_time = Some(authored_at);
 
         // Recalculate streak
-        self.app_state.current_streak =
-            calculate_streak_from_history(&self.app_state.commit_history);
+        self.app_state.current_streak = calculate_streak_from_history(
+            &self.app_state.commit_history,
+            self.state_manager.rules(),
+        );
 
         // Update best streak if needed
         if self.app_state.current_streak > self.app_state.best_streak {
             self.app_state.best_streak = self.app_state.current_streak;
         }
 
-        self.crab.celebrate();
+        // Breaking changes always get a big party, reverts a small one
+        match &conventional {
+            Some(c) if c.breaking => size = size.max(CommitSize::Large),
+            Some(c) if c.commit_type == "revert" => size = CommitSize::Tiny,
+            _ => {}
+        }
+
+        // Bigger commits get a longer, bouncier celebration
+        let (seconds, jumps) = match size {
+            CommitSize::Tiny => (1.5, 1),
+            CommitSize::Small => (3.0, 1),
+            CommitSize::Medium => (4.0, 2),
+            CommitSize::Large => (5.0, 3),
+            CommitSize::Huge => (6.0, 4),
+        };
+        self.crab.celebrate_with(seconds, jumps);
 
         // Update app state
         self.app_state.total_commits_tracked += 1;
+        self.app_state.record_languages(diff.as_ref());
+
+        // Show a commit reaction message
+        let message = if removed_markers {
+            messages::get_cleanup_message()
+        } else {
+            conventional
+                .as_ref()
+                .and_then(messages::get_commit_type_message)
+                .unwrap_or_else(|| messages::get_commit_message(size))
+        };
+        self.set_temp_message(message);
+
+        // Enough commits in a language unlock a new look
+        if let Some(accessory) = self
+            .crab
+            .set_wardrobe(&self.app_state.language_totals)
+            .first()
+        {
+            self.set_temp_message(&format!(
+                "New look unlocked: {}! ({} {} commits)",
+                accessory.name, accessory.commits, accessory.language
+            ));
+        }
+    }
+
+    /// Called with the tags detected in one check; a burst of tags is one party
+    fn on_new_releases(&mut self, tags: Vec<DetectedTag>) {
+        let mut names = Vec::new();
+        for tag in tags {
+            let name = tag.name.clone();
+            // Re-pointed or already recorded tags aren't new releases
+            if self.app_state.record_release(ReleaseEvent::from(tag)) {
+                names.push(name);
+            }
+        }
+        let Some(first) = names.first() else {
+            return;
+        };
 
-        // Show a commit reaction message for 30 seconds
-        self.set_temp_message(messages::get_commit_message());
+        let label = match names.len() {
+            1 => first.clone(),
+            n => format!("{} +{} more", first, n - 1),
+        };
+
+        // The biggest party Kani throws
+        self.crab.celebrate_with(8.0, 6);
+        self.environment.launch_fireworks(6);
+        self.set_temp_message(&format!("{} ({})", messages::get_release_message(), label));
+    }
+
+    /// Called when unpushed commits reach a remote-tracking ref
+    fn on_push(&mut self, push: DetectedPush) {
+        self.crab.celebrate_with(3.0, 2);
+        self.last_push_nag = None;
+        self.set_temp_message(&format!(
+            "{} ({} commit{} to {})",
+            messages::get_push_message(),
+            push.commit_count,
+            if push.commit_count == 1 { "" } else { "s" },
+            push.project_name
+        ));
+    }
+
+    /// Let Kani worry about large or stale uncommitted changes
+    fn update_anxiety(&mut self) {
+        let status = self.git_stats.working_tree_total();
+        self.crab.set_anxiety_target(anxiety_for(&status));
     }
 
     /// Check for mood changes and react with messages
     fn check_mood_change(&mut self) {
-        let current_mood = Mood::from_happiness(self.crab.happiness);
+        let current_mood = self.crab.mood;
 
         if current_mood != self.last_mood {
             // Mood changed - show a reaction message
-            let message = if current_mood as u8 > self.last_mood as u8 {
+            let message = if current_mood == Mood::Anxious {
+                messages::get_mood_message(Mood::Anxious)
+            } else if self.last_mood == Mood::Anxious {
+                // Worry went away
+                messages::get_mood_up_message()
+            } else if current_mood as u8 > self.last_mood as u8 {
                 // Mood went down (higher enum value = worse mood)
                 messages::get_mood_down_message()
             } else {
@@ -881,16 +1530,55 @@ impl App {
         if self.temp_message.is_none()
             && self.last_message_change.elapsed() > Duration::from_secs(120)
         {
-            let mood = Mood::from_happiness(self.crab.happiness);
-            self.current_message = messages::get_mood_message(mood).to_string();
+            let mood = self.crab.mood;
+            self.current_message = match self.stale_unpushed_repo() {
+                Some(repo) => format!("{} ({})", messages::get_unpushed_message(), repo),
+                None => self
+                    .insight_message()
+                    .unwrap_or_else(|| messages::get_mood_message(mood).to_string()),
+            };
             self.last_message_change = Instant::now();
         }
     }
 
-    /// Set a temporary message that shows for 30 seconds
+    /// Find a repo whose unpushed work is overdue, at most once per nag interval
+    fn stale_unpushed_repo(&mut self) -> Option<String> {
+        let nag_after = self.push_nag_after?;
+        if self
+            .last_push_nag
+            .is_some_and(|last| last.elapsed() < PUSH_NAG_INTERVAL)
+        {
+            return None;
+        }
+
+        let repo = self.git_stats.oldest_unpushed()?;
+        let oldest = repo.oldest_unpushed?;
+        if Local::now().signed_duration_since(oldest) < nag_after {
+            return None;
+        }
+        self.last_push_nag = Some(Instant::now());
+        Some(repo.name.clone())
+    }
+
+    /// Occasionally share an observation about the user's commit habits
+    fn insight_message(&self) -> Option<String> {
+        if rand::random::<f32>() >= INSIGHT_CHANCE {
+            return None;
+        }
+        let analytics = CommitAnalytics::from_history(
+            &self.app_state.commit_history,
+            self.state_manager.rules(),
+        );
+        if analytics.total() < MIN_COMMITS_FOR_INSIGHTS {
+            return None;
+        }
+        messages::get_insight_message(&analytics)
+    }
+
+    /// Set a temporary message that shows for `behavior.message_secs`
     fn set_temp_message(&mut self, message: &str) {
         self.temp_message = Some(message.to_string());
-        self.temp_message_until = Some(Instant::now() + Duration::from_secs(30));
+        self.temp_message_until = Some(Instant::now() + self.temp_message_duration);
     }
 
     /// Get the current message to display
@@ -905,10 +1593,12 @@ impl App {
         self.git_stats = self.git_tracker.get_stats();
     }
 
-    /// Sync happiness based on today's commit count
+    /// Sync happiness based on today's commits
     fn sync_happiness_from_commits(&mut self) {
-        let commits_today = get_today_commit_count(&self.app_state.commit_history);
-        let happiness = calculate_happiness_from_commits(commits_today);
+        let happiness = calculate_happiness_from_history(
+            &self.app_state.commit_history,
+            self.state_manager.rules(),
+        );
         self.crab.happiness = happiness;
         self.app_state.happiness = happiness;
     }
@@ -925,10 +1615,35 @@ impl App {
     /// Save application state
     fn save_state(&mut self) -> Result<()> {
         self.app_state.happiness = self.crab.happiness;
-        self.state_manager.save(&self.app_state)?;
+        self.state_manager.save(&mut self.app_state)?;
+        self.state_modified = self.state_manager.modified();
+        self.crab.set_wardrobe(&self.app_state.language_totals);
         Ok(())
     }
 
+    /// Merge the state file when another instance (e.g. `kanitomo -g tetris` in
+    /// another pane) saved it since we last did
+    fn check_state_file(&mut self) {
+        if self.last_state_check.elapsed() < STATE_CHECK_INTERVAL {
+            return;
+        }
+        self.last_state_check = Instant::now();
+
+        let modified = self.state_manager.modified();
+        if modified == self.state_modified {
+            return;
+        }
+        // A file that doesn't parse is left for the next save to replace
+        if self
+            .state_manager
+            .merge_from_disk(&mut self.app_state)
+            .is_ok()
+        {
+            self.state_modified = modified;
+            self.crab.set_wardrobe(&self.app_state.language_totals);
+        }
+    }
+
     /// Draw the UI
     fn draw(&mut self, frame: &mut Frame) {
         let area = frame.area();
@@ -948,7 +1663,7 @@ impl App {
         ];
 
         if show_stats_panel {
-            constraints.push(Constraint::Length(12));
+            constraints.push(Constraint::Length(13));
         }
 
         let chunks = Layout::default()
@@ -1009,6 +1724,8 @@ impl App {
                     frame,
                     &self.git_stats,
                     &self.app_state,
+                    self.state_manager.rules(),
+                    self.crab.mood,
                     self.crab.happiness,
                     chunks[2],
                 );
@@ -1017,11 +1734,47 @@ impl App {
 
         // Render overlays
         if self.show_repo_list {
-            widgets::render_repo_list(frame, &self.git_stats.repo_names, area);
+            widgets::render_repo_list(frame, &self.git_stats.repos, self.repo_list_selected, area);
         }
 
         if self.show_details {
-            widgets::render_details_overlay(frame, &self.app_state, area);
+            widgets::render_details_overlay(
+                frame,
+                &self.app_state,
+                self.state_manager.rules(),
+                self.details_selected,
+                area,
+            );
+        }
+
+        if self.show_heatmap {
+            widgets::render_heatmap_overlay(
+                frame,
+                &self.app_state,
+                self.state_manager.rules(),
+                self.heatmap_year,
+                self.heatmap_selected,
+                self.heatmap_project
+                    .as_ref()
+                    .map(|(id, name)| (id.as_str(), name.as_str())),
+                area,
+            );
+        }
+
+        if let Some(analytics) = &self.analytics {
+            widgets::render_analytics_overlay(frame, analytics, area);
+        }
+
+        if let Some((project_id, project_name)) = &self.project_view {
+            widgets::render_project_overlay(
+                frame,
+                &self.app_state,
+                self.state_manager.rules(),
+                project_id,
+                project_name,
+                &self.project_view_commits,
+                area,
+            );
         }
 
         if self.show_help {
@@ -1337,16 +2090,25 @@ impl App {
             // Add the commit to history
             let tracked = TrackedCommit {
                 timestamp: commit.timestamp,
+                committed_at: Some(commit.timestamp),
+                detected_at: Some(Local::now()),
                 commit_hash: commit.hash.clone(),
                 project_id: commit.project_id.clone(),
                 project_name: commit.project_name.clone(),
+                author_name: commit.author_name.clone(),
+                author_email: commit.author_email.clone(),
+                diff: None,
+                conventional: parse_conventional(&commit.message),
+                markers: None,
             };
             self.app_state.commit_history.push(tracked);
             self.app_state.last_commit_time = Some(commit.timestamp);
 
             // Recalculate streak
-            self.app_state.current_streak =
-                calculate_streak_from_history(&self.app_state.commit_history);
+            self.app_state.current_streak = calculate_streak_from_history(
+                &self.app_state.commit_history,
+                self.state_manager.rules(),
+            );
             if self.app_state.current_streak > self.app_state.best_streak {
                 self.app_state.best_streak = self.app_state.current_streak;
             }
@@ -1357,3 +2119,107 @@ impl App {
         }
     }
 }
+
+/// Whether a watcher event can add or remove a repository: a directory was
+/// created, removed or renamed (backends that can't tell files from directories
+/// report `Any`)
+fn is_layout_event(kind: &notify::EventKind) -> bool {
+    use notify::event::{CreateKind, ModifyKind, RemoveKind};
+    use notify::EventKind;
+
+    matches!(
+        kind,
+        EventKind::Create(CreateKind::Folder | CreateKind::Any)
+            | EventKind::Remove(RemoveKind::Folder | RemoveKind::Any)
+            | EventKind::Modify(ModifyKind::Name(_))
+    )
+}
+
+/// The scanned directories to watch for new repositories, shallowest first and at
+/// most `MAX_SCAN_DIR_WATCHES` of them, and whether any had to be left out
+fn scan_dirs_to_watch(dirs: &[PathBuf]) -> (Vec<&PathBuf>, bool) {
+    let mut dirs: Vec<&PathBuf> = dirs.iter().collect();
+    dirs.sort_by_key(|dir| dir.components().count());
+    let over_cap = dirs.len() > MAX_SCAN_DIR_WATCHES;
+    dirs.truncate(MAX_SCAN_DIR_WATCHES);
+    (dirs, over_cap)
+}
+
+/// How anxious uncommitted changes make Kani (0.0-1.0), from their size or age
+fn anxiety_for(status: &WorkingTreeStatus) -> f32 {
+    let size = status.changed_files() as f32 / ANXIOUS_CHANGED_FILES;
+    let age = status
+        .oldest_change
+        .map(|oldest| Local::now().signed_duration_since(oldest).num_minutes() as f32 / 60.0)
+        .unwrap_or(0.0)
+        / ANXIOUS_UNCOMMITTED_HOURS;
+    size.max(age).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anxiety_grows_with_size_and_age() {
+        assert_eq!(anxiety_for(&WorkingTreeStatus::default()), 0.0);
+
+        let half_full = WorkingTreeStatus {
+            modified: 10,
+            staged: 5,
+            untracked: 5,
+            oldest_change: Some(Local::now()),
+        };
+        assert!((anxiety_for(&half_full) - 0.5).abs() < 0.01);
+
+        // A couple of files left alone for half the limit
+        let stale = WorkingTreeStatus {
+            modified: 1,
+            oldest_change: Some(Local::now() - chrono::Duration::hours(2)),
+            ..Default::default()
+        };
+        assert!((anxiety_for(&stale) - 0.5).abs() < 0.01);
+
+        // Either one alone maxes out
+        let huge = WorkingTreeStatus {
+            untracked: 500,
+            ..Default::default()
+        };
+        assert_eq!(anxiety_for(&huge), 1.0);
+        let ancient = WorkingTreeStatus {
+            staged: 1,
+            oldest_change: Some(Local::now() - chrono::Duration::days(30)),
+            ..Default::default()
+        };
+        assert_eq!(anxiety_for(&ancient), 1.0);
+    }
+
+    #[test]
+    fn test_scan_dir_watches_are_capped_to_the_shallowest() {
+        let root = PathBuf::from("/home/me/code");
+        let few = vec![root.clone(), root.join("work")];
+        assert_eq!(scan_dirs_to_watch(&few), (vec![&few[0], &few[1]], false));
+
+        // Deep levels come first in scan order but are the ones left to rescans
+        let mut many = vec![root.clone()];
+        for i in 0..MAX_SCAN_DIR_WATCHES {
+            many.push(root.join(format!("group-{i}")).join("nested"));
+        }
+        many.push(root.join("work"));
+        let (watched, over_cap) = scan_dirs_to_watch(&many);
+        assert!(over_cap);
+        assert_eq!(watched.len(), MAX_SCAN_DIR_WATCHES);
+        assert_eq!(watched[..2], [&root, &root.join("work")]);
+    }
+
+    #[test]
+    fn test_changes_from_the_future_dont_calm_kani() {
+        // Clock skew (e.g. files from another machine) must not go negative
+        let skewed = WorkingTreeStatus {
+            modified: 4,
+            oldest_change: Some(Local::now() + chrono::Duration::hours(3)),
+            ..Default::default()
+        };
+        assert!((anxiety_for(&skewed) - 0.1).abs() < 0.01);
+    }
+}
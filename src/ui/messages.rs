@@ -1,4 +1,7 @@
 use crate::crab::Mood;
+use crate::git::{CommitSize, ConventionalCommit};
+use crate::state::{Chronotype, CommitAnalytics};
+use chrono::Weekday;
 use rand::seq::SliceRandom;
 
 /// Messages Kani says based on mood
@@ -31,6 +34,15 @@ const NEUTRAL_MESSAGES: &[&str] = &[
     "Standing by!",
 ];
 
+const ANXIOUS_MESSAGES: &[&str] = &[
+    "So many changes... shouldn't we commit?",
+    "That diff is getting big...",
+    "What if the power goes out?!",
+    "Small commits, please! My shell can't take it.",
+    "Uncommitted work makes my claws sweat...",
+    "git add -p? Just a thought...",
+];
+
 const SAD_MESSAGES: &[&str] = &[
     "I miss your commits...",
     "It's been a while...",
@@ -49,6 +61,15 @@ const HUNGRY_MESSAGES: &[&str] = &[
     "A little code would help...",
 ];
 
+/// Messages for a tiny commit (typo fixes, one-liners)
+const TINY_COMMIT_MESSAGES: &[&str] = &[
+    "A little snack!",
+    "Every crumb counts!",
+    "Tiny but tasty!",
+    "Typo squashed!",
+    "A nibble, thanks!",
+];
+
 /// Messages when user makes a commit
 const COMMIT_MESSAGES: &[&str] = &[
     "Yum, thanks for the meal!",
@@ -61,6 +82,126 @@ const COMMIT_MESSAGES: &[&str] = &[
     "That was great!",
 ];
 
+/// Messages for a medium-sized commit
+const MEDIUM_COMMIT_MESSAGES: &[&str] = &[
+    "Now that's a proper meal!",
+    "A hearty commit!",
+    "Solid work, I'm full!",
+    "That was filling!",
+];
+
+/// Messages for a large commit
+const LARGE_COMMIT_MESSAGES: &[&str] = &[
+    "What a feast!",
+    "So much code, so little shell!",
+    "That's a big one!",
+    "I'll need a nap after that!",
+];
+
+/// Messages for a huge commit (big refactors, generated code)
+const HUGE_COMMIT_MESSAGES: &[&str] = &[
+    "WHOA! A whole banquet!",
+    "That's a mega refactor!",
+    "I can't believe I ate the whole thing!",
+    "Did you rewrite everything?!",
+];
+
+/// Messages for a `feat:` commit
+const FEAT_MESSAGES: &[&str] = &[
+    "Ooh, something new!",
+    "A shiny new feature!",
+    "New toys to play with!",
+    "Ship it!",
+];
+
+/// Messages for a `fix:` commit
+const FIX_MESSAGES: &[&str] = &[
+    "Bug squashed!",
+    "One less bug in the sand!",
+    "Fixed it, nice pinch!",
+    "The bugs fear you!",
+];
+
+/// Messages for a `docs:` commit
+const DOCS_MESSAGES: &[&str] = &[
+    "Future you says thanks!",
+    "Docs! How thoughtful.",
+    "Reading material, yum!",
+];
+
+/// Messages for a `refactor:` commit
+const REFACTOR_MESSAGES: &[&str] = &[
+    "So tidy now!",
+    "Shell polished!",
+    "Cleaner code, happier crab!",
+];
+
+/// Messages for a `test:` commit
+const TEST_MESSAGES: &[&str] = &[
+    "Tests make me feel safe!",
+    "Green checks, green seaweed!",
+    "More tests, fewer surprises!",
+];
+
+/// Messages for a `perf:` commit
+const PERF_MESSAGES: &[&str] = &["Zoom zoom!", "Faster than a sideways sprint!", "Speedy!"];
+
+/// Messages for a `chore:`, `build:` or `ci:` commit
+const CHORE_MESSAGES: &[&str] = &[
+    "Chores done, good crab!",
+    "Keeping the tide pool clean!",
+    "Housekeeping, nice!",
+];
+
+/// Messages for a `revert:` commit
+const REVERT_MESSAGES: &[&str] = &[
+    "Undo! Undo!",
+    "Back to how it was...",
+    "Sometimes you scuttle backwards.",
+    "That one didn't work out, huh?",
+];
+
+/// Messages for a breaking change (`feat!:` or a BREAKING CHANGE footer)
+const BREAKING_MESSAGES: &[&str] = &[
+    "Breaking change! Brace the shell!",
+    "Whoa, big changes ahead!",
+    "Major version incoming!",
+    "Hold on to your claws!",
+];
+
+/// Messages when a tag (release) is created
+const RELEASE_MESSAGES: &[&str] = &[
+    "IT'S RELEASE DAY!",
+    "Shipped it! Fireworks for everyone!",
+    "A new version is out in the wild!",
+    "Release party on the beach!",
+    "Ship it, ship it, SHIPPED!",
+];
+
+/// Messages when local commits reach a remote
+const PUSH_MESSAGES: &[&str] = &[
+    "Pushed! Safe and sound upstream.",
+    "Off they go, into the sea!",
+    "Your commits made it to the remote!",
+    "Backed up! I can relax now.",
+];
+
+/// Gentle reminders about commits that have been waiting to be pushed
+const UNPUSHED_MESSAGES: &[&str] = &[
+    "Psst... some commits are still waiting to be pushed.",
+    "Your commits miss the remote...",
+    "Don't forget to push!",
+    "Unpushed work makes me nervous...",
+];
+
+/// Messages when a commit removes TODO/FIXME/HACK markers
+const CLEANUP_MESSAGES: &[&str] = &[
+    "One less TODO! So tidy!",
+    "Cleaning up old FIXMEs? I love it!",
+    "Fewer hacks, happier crab!",
+    "Paying off that debt, nice!",
+];
+
 /// Messages when mood improves
 const MOOD_UP_MESSAGES: &[&str] = &[
     "I'm feeling better!",
@@ -84,6 +225,7 @@ pub fn get_mood_message(mood: Mood) -> &'static str {
         Mood::Ecstatic => ECSTATIC_MESSAGES,
         Mood::Happy => HAPPY_MESSAGES,
         Mood::Neutral => NEUTRAL_MESSAGES,
+        Mood::Anxious => ANXIOUS_MESSAGES,
         Mood::Sad => SAD_MESSAGES,
         Mood::Hungry => HUNGRY_MESSAGES,
     };
@@ -91,13 +233,71 @@ pub fn get_mood_message(mood: Mood) -> &'static str {
     messages.choose(&mut rand::thread_rng()).unwrap_or(&"...")
 }
 
-/// Get a random message for when user commits
-pub fn get_commit_message() -> &'static str {
-    COMMIT_MESSAGES
+/// Get a random message for when user commits, scaled to the commit size
+pub fn get_commit_message(size: CommitSize) -> &'static str {
+    let messages = match size {
+        CommitSize::Tiny => TINY_COMMIT_MESSAGES,
+        CommitSize::Small => COMMIT_MESSAGES,
+        CommitSize::Medium => MEDIUM_COMMIT_MESSAGES,
+        CommitSize::Large => LARGE_COMMIT_MESSAGES,
+        CommitSize::Huge => HUGE_COMMIT_MESSAGES,
+    };
+
+    messages
         .choose(&mut rand::thread_rng())
         .unwrap_or(&"Thanks!")
 }
 
+/// Get a random message reacting to a Conventional Commit type
+/// Returns None for types without a dedicated pool
+pub fn get_commit_type_message(conventional: &ConventionalCommit) -> Option<&'static str> {
+    let messages = if conventional.breaking {
+        BREAKING_MESSAGES
+    } else {
+        match conventional.commit_type.as_str() {
+            "feat" => FEAT_MESSAGES,
+            "fix" => FIX_MESSAGES,
+            "docs" => DOCS_MESSAGES,
+            "refactor" => REFACTOR_MESSAGES,
+            "test" => TEST_MESSAGES,
+            "perf" => PERF_MESSAGES,
+            "chore" | "build" | "ci" => CHORE_MESSAGES,
+            "revert" => REVERT_MESSAGES,
+            _ => return None,
+        }
+    };
+
+    messages.choose(&mut rand::thread_rng()).copied()
+}
+
+/// Get a random message for a new release
+pub fn get_release_message() -> &'static str {
+    RELEASE_MESSAGES
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&"Release!")
+}
+
+/// Get a random message for a push landing
+pub fn get_push_message() -> &'static str {
+    PUSH_MESSAGES
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&"Pushed!")
+}
+
+/// Get a random reminder about unpushed commits
+pub fn get_unpushed_message() -> &'static str {
+    UNPUSHED_MESSAGES
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&"Don't forget to push!")
+}
+
+/// Get a random message for a commit that removed TODO markers
+pub fn get_cleanup_message() -> &'static str {
+    CLEANUP_MESSAGES
+        .choose(&mut rand::thread_rng())
+        .unwrap_or(&"One less TODO!")
+}
+
 /// Get a random message for mood improvement
 pub fn get_mood_up_message() -> &'static str {
     MOOD_UP_MESSAGES
@@ -111,3 +311,47 @@ pub fn get_mood_down_message() -> &'static str {
         .choose(&mut rand::thread_rng())
         .unwrap_or(&"...")
 }
+
+/// Get a random observation about when the user tends to commit
+/// Returns None until there is enough history to say anything
+pub fn get_insight_message(analytics: &CommitAnalytics) -> Option<String> {
+    let mut insights = Vec::new();
+
+    if let Some(hour) = analytics.peak_hour() {
+        let (display_hour, suffix) = match hour {
+            0 => (12, "am"),
+            1..=11 => (hour, "am"),
+            12 => (12, "pm"),
+            _ => (hour - 12, "pm"),
+        };
+        insights.push(format!(
+            "You're usually on fire around {}{}!",
+            display_hour, suffix
+        ));
+    }
+
+    if let Some(day) = analytics.busiest_weekday() {
+        let name = match day {
+            Weekday::Mon => "Mondays",
+            Weekday::Tue => "Tuesdays",
+            Weekday::Wed => "Wednesdays",
+            Weekday::Thu => "Thursdays",
+            Weekday::Fri => "Fridays",
+            Weekday::Sat => "Saturdays",
+            Weekday::Sun => "Sundays",
+        };
+        insights.push(format!("{} are your busiest days!", name));
+    }
+
+    match analytics.chronotype {
+        Some(Chronotype::MorningCoder) => {
+            insights.push("Early crab catches the bug! You're a morning coder.".to_string())
+        }
+        Some(Chronotype::NightCoder) => {
+            insights.push("A fellow creature of the night! You're a night coder.".to_string())
+        }
+        None => {}
+    }
+
+    insights.choose(&mut rand::thread_rng()).cloned()
+}
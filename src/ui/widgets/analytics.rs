@@ -0,0 +1,193 @@
+use crate::state::CommitAnalytics;
+use chrono::Duration;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::helpers::centered_rect;
+
+/// Height of the hour-of-day histogram in rows
+const HOUR_CHART_ROWS: usize = 5;
+
+/// Partial block characters, from empty to full
+const BAR_LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+const WEEKDAY_NAMES: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+/// Render the productivity analytics overlay
+pub fn render_analytics_overlay(frame: &mut Frame, analytics: &CommitAnalytics, area: Rect) {
+    let mut lines: Vec<Line> = vec![Line::from("")];
+
+    if analytics.total() == 0 {
+        lines.push(Line::from(vec![Span::styled(
+            "  No commits recorded yet",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )]));
+    } else {
+        // Commits by hour of day (three columns per hour)
+        lines.push(section_header("  BY HOUR OF DAY"));
+        let max_hour = analytics.by_hour.iter().copied().max().unwrap_or(0).max(1);
+        let peak_hour = analytics.peak_hour();
+        for row in 0..HOUR_CHART_ROWS {
+            let floor = (HOUR_CHART_ROWS - 1 - row) * 8;
+            let mut spans = vec![Span::raw("    ")];
+            for (hour, count) in analytics.by_hour.iter().enumerate() {
+                let level = if *count == 0 {
+                    0
+                } else {
+                    ((*count as usize * HOUR_CHART_ROWS * 8) / max_hour as usize).max(1)
+                };
+                let fill = level.saturating_sub(floor).min(8);
+                let color = if peak_hour == Some(hour as u32) {
+                    Color::Yellow
+                } else {
+                    Color::Green
+                };
+                spans.push(Span::styled(
+                    format!("{} ", BAR_LEVELS[fill].to_string().repeat(2)),
+                    Style::default().fg(color),
+                ));
+            }
+            lines.push(Line::from(spans));
+        }
+        let hour_labels: String = (0..24)
+            .map(|hour| {
+                if hour % 3 == 0 {
+                    format!("{:<3}", hour)
+                } else {
+                    "   ".to_string()
+                }
+            })
+            .collect();
+        lines.push(Line::from(vec![Span::styled(
+            format!("    {}", hour_labels),
+            Style::default().fg(Color::DarkGray),
+        )]));
+        lines.push(Line::from(""));
+
+        // Commits by weekday
+        lines.push(section_header("  BY WEEKDAY"));
+        let max_day = analytics
+            .by_weekday
+            .iter()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for (name, count) in WEEKDAY_NAMES.iter().zip(analytics.by_weekday) {
+            let bar_len = (count as usize * 30) / max_day as usize;
+            let bar = if count > 0 {
+                "█".repeat(bar_len.max(1))
+            } else {
+                "░".to_string()
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<6}", name),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(format!("{:<31}", bar), Style::default().fg(Color::Magenta)),
+                Span::styled(
+                    format!("{} commit{}", count, if count == 1 { "" } else { "s" }),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+        lines.push(Line::from(""));
+
+        // Summary numbers
+        lines.push(section_header("  RHYTHM"));
+        let format_time = |time: Option<chrono::NaiveTime>| {
+            time.map_or("-".to_string(), |t| t.format("%H:%M").to_string())
+        };
+        let rows = [
+            (
+                "Median time between commits",
+                analytics
+                    .median_gap
+                    .map_or("-".to_string(), format_duration),
+            ),
+            (
+                "Average first commit",
+                format_time(analytics.average_first_commit),
+            ),
+            (
+                "Average last commit",
+                format_time(analytics.average_last_commit),
+            ),
+            (
+                "You are a",
+                analytics
+                    .chronotype
+                    .map_or("bit of both (so far)".to_string(), |c| c.to_string()),
+            ),
+        ];
+        for (label, value) in rows {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<30}", label),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    value,
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  Press [i] or [q] to close",
+        Style::default().fg(Color::DarkGray),
+    )]));
+
+    let overlay_width = 80.min(area.width.saturating_sub(4));
+    let overlay_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let overlay_area = centered_rect(overlay_width, overlay_height, area);
+
+    // Clear the area behind the overlay
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            " Analytics ",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, overlay_area);
+}
+
+fn section_header(title: &str) -> Line<'_> {
+    Line::from(vec![Span::styled(
+        title,
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )])
+}
+
+/// Format a duration as "3h 20m", "45m" or "2d 4h"
+fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes();
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 24 * 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{}d {}h", minutes / (24 * 60), (minutes / 60) % 24)
+    }
+}
@@ -0,0 +1,215 @@
+use crate::crab::Mood;
+use crate::state::{
+    calculate_happiness_from_commits, get_day_projects, get_year_activity, AppState, Rules,
+};
+use chrono::{Datelike, Duration, NaiveDate};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::helpers::{centered_rect, truncate_str};
+
+/// Weekday labels down the left side (Mon-based weeks, like the week summary)
+const WEEKDAY_LABELS: [&str; 7] = ["Mon ", "    ", "Wed ", "    ", "Fri ", "    ", "Sun "];
+
+/// Render the yearly contribution heatmap overlay
+/// `project` filters the heatmap to one project, as (project id, display name)
+pub fn render_heatmap_overlay(
+    frame: &mut Frame,
+    app_state: &AppState,
+    rules: &Rules,
+    year: i32,
+    selected: NaiveDate,
+    project: Option<(&str, &str)>,
+    area: Rect,
+) {
+    let project_id = project.map(|(id, _)| id);
+    let activity = get_year_activity(&app_state.commit_history, year, project_id, rules);
+    let total: u32 = activity.values().sum();
+    let today = rules.today();
+
+    // Weeks start on the Monday on or before January 1st
+    let Some(jan_first) = NaiveDate::from_ymd_opt(year, 1, 1) else {
+        return;
+    };
+    let grid_start = jan_first - Duration::days(jan_first.weekday().num_days_from_monday() as i64);
+    let days_in_year = if NaiveDate::from_ymd_opt(year, 2, 29).is_some() {
+        366
+    } else {
+        365
+    };
+    let weeks = (jan_first.weekday().num_days_from_monday() as usize + days_in_year).div_ceil(7);
+
+    // Two columns per week when the terminal is wide enough, one otherwise
+    let cell_width = if area.width as usize >= weeks * 2 + 10 {
+        2
+    } else {
+        1
+    };
+    let overlay_width = ((weeks * cell_width + 10) as u16).min(area.width.saturating_sub(2));
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(
+                "  {} commit{} in {}",
+                total,
+                if total == 1 { "" } else { "s" },
+                year
+            ),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("   Project: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            project.map_or("all".to_string(), |(_, name)| truncate_str(name, 30)),
+            Style::default().fg(Color::Cyan),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    // Month labels above the first week containing the 1st of each month
+    let mut month_row = vec![' '; weeks * cell_width];
+    for month in 1..=12 {
+        let Some(first) = NaiveDate::from_ymd_opt(year, month, 1) else {
+            continue;
+        };
+        let column = ((first - grid_start).num_days() / 7) as usize * cell_width;
+        let label = first.format("%b").to_string();
+        for (offset, ch) in label.chars().enumerate() {
+            if let Some(slot) = month_row.get_mut(column + offset) {
+                *slot = ch;
+            }
+        }
+    }
+    lines.push(Line::from(vec![
+        Span::raw("      "),
+        Span::styled(
+            month_row.into_iter().collect::<String>(),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]));
+
+    for (weekday, label) in WEEKDAY_LABELS.iter().enumerate() {
+        let mut spans = vec![
+            Span::raw("  "),
+            Span::styled(*label, Style::default().fg(Color::DarkGray)),
+        ];
+        for week in 0..weeks {
+            let date = grid_start + Duration::days((week * 7 + weekday) as i64);
+            if date.year() != year || date > today {
+                spans.push(Span::raw(" ".repeat(cell_width)));
+                continue;
+            }
+
+            let count = activity.get(&date).copied().unwrap_or(0);
+            let (ch, color) = cell_shade(count, rules);
+            let mut style = Style::default().fg(color);
+            if date == selected {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
+            spans.push(Span::styled(ch.to_string().repeat(cell_width), style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines.push(Line::from(""));
+
+    // Selected day details
+    let selected_count = activity.get(&selected).copied().unwrap_or(0);
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("  {}", selected.format("%a %b %d %Y")),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  {} commit{}",
+                selected_count,
+                if selected_count == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(Color::White),
+        ),
+    ]));
+    let day_projects = get_day_projects(&app_state.commit_history, selected, project_id, rules);
+    let projects_text = if day_projects.is_empty() {
+        "no commits".to_string()
+    } else {
+        day_projects
+            .iter()
+            .map(|(name, count)| format!("{} ({})", name, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    lines.push(Line::from(vec![Span::styled(
+        format!(
+            "  {}",
+            truncate_str(&projects_text, overlay_width.saturating_sub(6) as usize)
+        ),
+        Style::default().fg(Color::DarkGray),
+    )]));
+    lines.push(Line::from(""));
+
+    // Legend
+    let mut legend = vec![Span::styled(
+        "  Less ",
+        Style::default().fg(Color::DarkGray),
+    )];
+    for count in [0, 1, 4, 8, 15] {
+        let (ch, color) = cell_shade(count, rules);
+        legend.push(Span::styled(
+            ch.to_string().repeat(cell_width),
+            Style::default().fg(color),
+        ));
+    }
+    legend.push(Span::styled(" More", Style::default().fg(Color::DarkGray)));
+    lines.push(Line::from(legend));
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  [hjkl] move  [ and ] year  [p] project  [y]/[q] close",
+        Style::default().fg(Color::DarkGray),
+    )]));
+
+    let overlay_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let overlay_area = centered_rect(overlay_width, overlay_height, area);
+
+    // Clear the area behind the overlay
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            format!(" Contributions {} ", year),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, overlay_area);
+}
+
+/// Shade and color for a day with `count` commits, using the mood Kani
+/// would be in after that many commits
+fn cell_shade(count: u32, rules: &Rules) -> (char, Color) {
+    if count == 0 {
+        return ('·', Color::DarkGray);
+    }
+    let mood = Mood::from_happiness(calculate_happiness_from_commits(count, rules));
+    let ch = match mood {
+        Mood::Ecstatic => '█',
+        Mood::Happy => '▓',
+        Mood::Neutral => '▒',
+        _ => '░',
+    };
+    (ch, mood.color())
+}
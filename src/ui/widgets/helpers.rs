@@ -177,3 +177,39 @@ pub(crate) fn render_happiness_bar(happiness: u8) -> Line<'static> {
         ),
     ])
 }
+
+/// Color for a language in language breakdowns (GitHub-like where it's well known)
+pub(crate) fn language_color(language: &str) -> Color {
+    match language {
+        "Rust" => Color::Rgb(206, 92, 38),
+        "Python" => Color::Rgb(53, 114, 165),
+        "JavaScript" => Color::Rgb(241, 224, 90),
+        "TypeScript" => Color::Rgb(49, 120, 198),
+        "Go" => Color::Rgb(0, 173, 216),
+        "Java" => Color::Rgb(176, 114, 25),
+        "C" => Color::Rgb(85, 85, 85),
+        "C++" => Color::Rgb(243, 75, 125),
+        "C#" => Color::Rgb(23, 134, 0),
+        "Ruby" => Color::Rgb(112, 21, 22),
+        "Haskell" => Color::Rgb(94, 80, 134),
+        "Shell" => Color::Rgb(137, 224, 81),
+        "HTML" => Color::Rgb(227, 76, 38),
+        "CSS" => Color::Rgb(86, 61, 124),
+        "Markdown" => Color::Rgb(8, 63, 161),
+        _ => {
+            // Stable pick for everything else
+            const PALETTE: [Color; 6] = [
+                Color::Cyan,
+                Color::Magenta,
+                Color::Green,
+                Color::Yellow,
+                Color::Blue,
+                Color::LightRed,
+            ];
+            let hash = language
+                .bytes()
+                .fold(0usize, |h, b| h.wrapping_mul(31) + b as usize);
+            PALETTE[hash % PALETTE.len()]
+        }
+    }
+}
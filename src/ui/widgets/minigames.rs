@@ -344,7 +344,10 @@ pub fn render_minigame_menu(frame: &mut Frame, area: Rect, app_state: &AppState)
                 .add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![Span::styled(
-            format!("Last commit: {}", format_time_ago(app_state.last_commit_time)),
+            format!(
+                "Last commit: {}",
+                format_time_ago(app_state.last_commit_time)
+            ),
             Style::default().fg(Color::White),
         )]),
         Line::from(""),
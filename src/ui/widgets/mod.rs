@@ -1,9 +1,14 @@
+mod analytics;
+mod heatmap;
 mod helpers;
 mod minigames;
 mod overlays;
+mod project;
 mod scene;
 mod stats;
 
+pub use analytics::render_analytics_overlay;
+pub use heatmap::render_heatmap_overlay;
 pub use minigames::{
     render_2048_game, render_2048_results, render_breakout_game, render_breakout_results,
     render_crab_catch, render_dash_game, render_dash_results, render_minigame_menu,
@@ -14,5 +19,6 @@ pub use overlays::{
     render_commit_picker, render_details_overlay, render_help_overlay, render_repo_list,
     render_title,
 };
+pub use project::render_project_overlay;
 pub use scene::{render_crab, render_environment_background, render_ground};
 pub use stats::render_stats;
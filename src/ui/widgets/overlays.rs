@@ -1,5 +1,8 @@
-use crate::git::{format_time_ago, CommitInfo};
-use crate::state::{get_today_by_project, get_week_summary, AppState};
+use crate::crab::{next_accessory, unlocked_accessories};
+use crate::git::{format_time_ago, CommitInfo, RepoInfo, WatchMode};
+use crate::state::{
+    get_today_by_project, get_type_breakdown, get_week_languages, get_week_summary, AppState, Rules,
+};
 use chrono::Datelike;
 use ratatui::{
     layout::{Alignment, Rect},
@@ -9,7 +12,10 @@ use ratatui::{
     Frame,
 };
 
-use super::helpers::{centered_rect, truncate_str};
+use super::helpers::{centered_rect, language_color, truncate_str};
+
+/// Languages listed under the weekly language bar
+const WEEK_LANGUAGES_SHOWN: usize = 4;
 
 /// Render the help overlay window
 pub fn render_help_overlay(
@@ -168,10 +174,10 @@ pub fn render_title(frame: &mut Frame, area: Rect, message: &str) {
 }
 
 /// Render the repo list overlay
-pub fn render_repo_list(frame: &mut Frame, repo_names: &[String], area: Rect) {
+pub fn render_repo_list(frame: &mut Frame, repos: &[RepoInfo], selected: usize, area: Rect) {
     // Calculate overlay size - center it in the screen
-    let overlay_width = 40.min(area.width.saturating_sub(4));
-    let overlay_height = (repo_names.len() as u16 + 4).min(area.height.saturating_sub(4));
+    let overlay_width = 70.min(area.width.saturating_sub(4));
+    let overlay_height = (repos.len() as u16 + 4).min(area.height.saturating_sub(4));
 
     let overlay_area = centered_rect(overlay_width, overlay_height, area);
 
@@ -181,17 +187,49 @@ pub fn render_repo_list(frame: &mut Frame, repo_names: &[String], area: Rect) {
     // Build the list of repos
     let mut lines: Vec<Line> = vec![Line::from("")];
 
-    for name in repo_names {
+    for (i, repo) in repos.iter().enumerate() {
+        let is_selected = i == selected;
         lines.push(Line::from(vec![
             Span::styled("  ", Style::default()),
-            Span::styled("  ", Style::default().fg(Color::Green)),
-            Span::styled(name.clone(), Style::default().fg(Color::White)),
+            Span::styled(
+                if is_selected { "> " } else { "  " },
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::styled(
+                format!("{:<22}", truncate_str(&repo.name, 22)),
+                if is_selected {
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                },
+            ),
+            Span::styled(
+                format!("{:<30}", truncate_str(&repo.source.to_string(), 30)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!("{:<9}", repo.watch_mode.to_string()),
+                Style::default().fg(match repo.watch_mode {
+                    WatchMode::Events => Color::Green,
+                    WatchMode::Polling => Color::Yellow,
+                }),
+            ),
+            Span::styled(
+                if repo.unpushed > 0 {
+                    format!("↑{}", repo.unpushed)
+                } else {
+                    String::new()
+                },
+                Style::default().fg(Color::Yellow),
+            ),
         ]));
     }
 
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
-        "  Press [a] or [q] to close",
+        "  [j/k] select  [enter] project view  [a]/[q] close",
         Style::default().fg(Color::DarkGray),
     )]));
 
@@ -210,15 +248,59 @@ pub fn render_repo_list(frame: &mut Frame, repo_names: &[String], area: Rect) {
 }
 
 /// Render the activity details overlay
-pub fn render_details_overlay(frame: &mut Frame, app_state: &AppState, area: Rect) {
-    let today_by_project = get_today_by_project(&app_state.commit_history);
-    let week_summary = get_week_summary(&app_state.commit_history);
+pub fn render_details_overlay(
+    frame: &mut Frame,
+    app_state: &AppState,
+    rules: &Rules,
+    selected: usize,
+    area: Rect,
+) {
+    let today_by_project = get_today_by_project(&app_state.commit_history, rules);
+    let week_summary = get_week_summary(&app_state.commit_history, rules);
+    let team_today = get_today_by_project(&app_state.team_activity, rules);
+    let type_breakdown = get_type_breakdown(&app_state.commit_history, rules);
+    let week_languages = get_week_languages(&app_state.commit_history, rules);
+    let recent_releases: Vec<_> = app_state.releases.iter().rev().take(5).collect();
+    let last_commit = app_state
+        .commit_history
+        .iter()
+        .max_by_key(|commit| commit.timestamp);
 
     // Calculate required height
     let today_lines = today_by_project.len().max(1) + 3; // projects + header + total + blank
     let week_lines = week_summary.len() + 2; // days + header + total
+    let team_lines = if team_today.is_empty() {
+        0
+    } else {
+        team_today.len() + 2 // projects + header + blank
+    };
+    let last_commit_lines = match last_commit {
+        Some(commit) if commit.diff.is_some() => 6, // header + 2 times + changes + languages + blank
+        Some(_) => 4,                               // header + 2 times + blank
+        None => 0,
+    };
+    // blank + header + bar + languages + wardrobe + next look
+    let language_lines = week_languages.len().min(WEEK_LANGUAGES_SHOWN) + 5;
+    let type_lines = if type_breakdown.is_empty() {
+        0
+    } else {
+        type_breakdown.len() + 2 // types + header + blank
+    };
+    let release_lines = if recent_releases.is_empty() {
+        0
+    } else {
+        recent_releases.len() + 2 // releases + header + blank
+    };
     let footer_lines = 2;
-    let total_height = (today_lines + week_lines + footer_lines + 4) as u16; // +4 for borders and spacing
+    let content_lines = today_lines
+        + week_lines
+        + language_lines
+        + type_lines
+        + release_lines
+        + last_commit_lines
+        + team_lines
+        + footer_lines;
+    let total_height = (content_lines + 4) as u16; // +4 for borders and spacing
 
     let overlay_width = 45.min(area.width.saturating_sub(4));
     let overlay_height = total_height.min(area.height.saturating_sub(4));
@@ -233,7 +315,7 @@ pub fn render_details_overlay(frame: &mut Frame, app_state: &AppState, area: Rec
     // Today section
     let today_total: u32 = today_by_project.iter().map(|(_, _, c)| c).sum();
     lines.push(Line::from(vec![Span::styled(
-        format!("  TODAY ({})", chrono::Local::now().format("%b %d")),
+        format!("  TODAY ({})", rules.today().format("%b %d")),
         Style::default()
             .fg(Color::Cyan)
             .add_modifier(Modifier::BOLD),
@@ -252,16 +334,24 @@ pub fn render_details_overlay(frame: &mut Frame, app_state: &AppState, area: Rec
             .map(|(_, _, c)| *c)
             .max()
             .unwrap_or(1);
-        for (_id, name, count) in &today_by_project {
+        for (i, (_id, name, count)) in today_by_project.iter().enumerate() {
             let bar_len = (*count as usize * 10) / max_count.max(1) as usize;
             let bar = "█".repeat(bar_len.max(1));
             let padding = " ".repeat(10 - bar_len.max(1));
 
+            let name_style = if i == selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
+            };
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("    {:<16}", truncate_str(name, 16)),
-                    Style::default().fg(Color::White),
+                    if i == selected { "  > " } else { "    " },
+                    Style::default().fg(Color::Yellow),
                 ),
+                Span::styled(format!("{:<16}", truncate_str(name, 16)), name_style),
                 Span::styled(bar, Style::default().fg(Color::Green)),
                 Span::styled(padding, Style::default()),
                 Span::styled(
@@ -302,7 +392,7 @@ pub fn render_details_overlay(frame: &mut Frame, app_state: &AppState, area: Rec
 
     let week_total: u32 = week_summary.iter().map(|(_, c)| c).sum();
     let max_day_count = week_summary.iter().map(|(_, c)| *c).max().unwrap_or(1);
-    let today_date = chrono::Local::now().date_naive();
+    let today_date = rules.today();
 
     for (date, count) in &week_summary {
         let day_name = match date.weekday() {
@@ -370,9 +460,250 @@ pub fn render_details_overlay(frame: &mut Frame, app_state: &AppState, area: Rec
         ),
     ]));
 
+    // Languages this week, as one bar split by language
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  LANGUAGES THIS WEEK",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    let language_total: u32 = week_languages.iter().map(|(_, c)| c).sum();
+    if language_total == 0 {
+        lines.push(Line::from(vec![Span::styled(
+            "    No code touched yet",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )]));
+    } else {
+        const BAR_WIDTH: usize = 30;
+        let mut bar = vec![Span::raw("    ")];
+        let mut used = 0;
+        for (i, (language, count)) in week_languages.iter().enumerate() {
+            // The last language takes whatever is left so the bar is always full
+            let width = if i == week_languages.len() - 1 {
+                BAR_WIDTH - used
+            } else {
+                ((*count as usize * BAR_WIDTH) / language_total as usize).min(BAR_WIDTH - used)
+            };
+            used += width;
+            bar.push(Span::styled(
+                "█".repeat(width),
+                Style::default().fg(language_color(language)),
+            ));
+        }
+        lines.push(Line::from(bar));
+
+        for (language, count) in week_languages.iter().take(WEEK_LANGUAGES_SHOWN) {
+            lines.push(Line::from(vec![
+                Span::styled("    █ ", Style::default().fg(language_color(language))),
+                Span::styled(
+                    format!("{:<14}", truncate_str(language, 14)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("{:>3}%", (*count as usize * 100) / language_total as usize),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    let wardrobe: Vec<&str> = unlocked_accessories(&app_state.language_totals)
+        .iter()
+        .map(|a| a.name)
+        .collect();
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!("    {:<10}", "Wardrobe"),
+            Style::default().fg(Color::White),
+        ),
+        Span::styled(
+            if wardrobe.is_empty() {
+                "-".to_string()
+            } else {
+                truncate_str(&wardrobe.join(", "), 28)
+            },
+            Style::default().fg(Color::Yellow),
+        ),
+    ]));
+    if let Some((accessory, done)) = next_accessory(&app_state.language_totals) {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("    {:<10}", "Next look"),
+                Style::default().fg(Color::White),
+            ),
+            Span::styled(
+                truncate_str(
+                    &format!(
+                        "{} ({}/{} {})",
+                        accessory.name, done, accessory.commits, accessory.language
+                    ),
+                    28,
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+
+    // Commit types section (Conventional Commits)
+    if !type_breakdown.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<20}", "COMMIT TYPES"),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("today   week", Style::default().fg(Color::DarkGray)),
+        ]));
+
+        for (commit_type, today, week) in &type_breakdown {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<18}", truncate_str(commit_type, 18)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(format!("{:>5}", today), Style::default().fg(Color::Green)),
+                Span::styled(format!("{:>7}", week), Style::default().fg(Color::Magenta)),
+            ]));
+        }
+    }
+
+    // Releases section (most recent tags)
+    if !recent_releases.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "  RELEASES",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]));
+
+        for release in &recent_releases {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<12}", truncate_str(&release.tag, 12)),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("{:<14}", truncate_str(&release.project_name, 14)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    release.created_at.format("%b %d").to_string(),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    // Last commit section (author time vs. when Kanitomo noticed it)
+    if let Some(commit) = last_commit {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            format!("  LAST COMMIT ({})", truncate_str(&commit.project_name, 20)),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]));
+
+        let times = [
+            ("Authored", Some(commit.timestamp)),
+            ("Detected", commit.detected_at),
+        ];
+        for (label, time) in times {
+            let value = match time {
+                Some(time) => format!(
+                    "{}  ({})",
+                    time.format("%b %d %H:%M"),
+                    format_time_ago(Some(time))
+                ),
+                None => "unknown".to_string(),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<10}", label),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(value, Style::default().fg(Color::DarkGray)),
+            ]));
+        }
+
+        if let Some(diff) = &commit.diff {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<10}", "Changes"),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!("+{}", diff.insertions),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(" ", Style::default()),
+                Span::styled(
+                    format!("-{}", diff.deletions),
+                    Style::default().fg(Color::Red),
+                ),
+                Span::styled(
+                    format!(
+                        " in {} file{}",
+                        diff.files_changed,
+                        if diff.files_changed == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+
+            let languages = if diff.languages.is_empty() {
+                "-".to_string()
+            } else {
+                diff.languages.join(", ")
+            };
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<10}", "Languages"),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    truncate_str(&languages, 28),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    // Team activity section (commits by other authors, not counted for Kani)
+    if !team_today.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![Span::styled(
+            "  TEAM ACTIVITY (today)",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]));
+
+        for (_id, name, count) in &team_today {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {:<16}", truncate_str(name, 16)),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!(" {} commit{}", count, if *count == 1 { "" } else { "s" }),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
     lines.push(Line::from(""));
     lines.push(Line::from(vec![Span::styled(
-        "  Press [d] or [q] to close",
+        "  [j/k] select  [enter] open  [d]/[q] close",
         Style::default().fg(Color::DarkGray),
     )]));
 
@@ -0,0 +1,309 @@
+use crate::git::{format_time_ago, CommitInfo};
+use crate::state::{get_project_summary, AppState, ProjectSummary, Rules};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::helpers::{centered_rect, truncate_str};
+
+/// Days shown in the per-day chart
+const CHART_DAYS: u32 = 30;
+/// Height of the per-day chart in rows
+const CHART_ROWS: usize = 4;
+
+/// Most recent scanned commits shown in the TODO trend
+const TREND_COMMITS: usize = 60;
+
+/// Partial block characters, from empty to full
+const BAR_LEVELS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Render the per-project dashboard overlay
+/// `commits` are the repository's latest commits (empty when the repo isn't tracked)
+pub fn render_project_overlay(
+    frame: &mut Frame,
+    app_state: &AppState,
+    rules: &Rules,
+    project_id: &str,
+    project_name: &str,
+    commits: &[CommitInfo],
+    area: Rect,
+) {
+    let summary = get_project_summary(&app_state.commit_history, project_id, CHART_DAYS, rules);
+
+    let overlay_width = 70.min(area.width.saturating_sub(4));
+
+    let mut lines: Vec<Line> = vec![Line::from("")];
+
+    // Headline numbers
+    lines.push(Line::from(vec![
+        Span::styled("  Streak: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(
+                "{} day{}",
+                summary.current_streak,
+                if summary.current_streak == 1 { "" } else { "s" }
+            ),
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled("   Total: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format!(
+                "{} commit{}",
+                summary.total,
+                if summary.total == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled("   Last: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format_time_ago(summary.last_commit),
+            Style::default().fg(Color::White),
+        ),
+    ]));
+    lines.push(Line::from(""));
+
+    // Commits per day
+    lines.push(Line::from(vec![Span::styled(
+        format!("  LAST {} DAYS", CHART_DAYS),
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    lines.extend(chart_lines(&summary));
+
+    let gap_days = summary.longest_gap_days();
+    let gap_text = match summary.longest_gap {
+        Some((from, to)) if gap_days > 0 => format!(
+            "{} day{} ({} - {})",
+            gap_days,
+            if gap_days == 1 { "" } else { "s" },
+            from.format("%b %d"),
+            to.format("%b %d")
+        ),
+        _ => "none".to_string(),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("  Longest gap: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(gap_text, Style::default().fg(Color::Red)),
+    ]));
+    lines.push(Line::from(""));
+
+    // TODO/FIXME/HACK markers after each commit
+    if !summary.marker_trend.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "  TODO / FIXME / HACK",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        lines.extend(marker_trend_lines(&summary));
+        lines.push(Line::from(""));
+    }
+
+    // Recent commit subjects
+    lines.push(Line::from(vec![Span::styled(
+        "  RECENT COMMITS",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )]));
+    if commits.is_empty() {
+        lines.push(Line::from(vec![Span::styled(
+            "    Repository not tracked right now",
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::ITALIC),
+        )]));
+    } else {
+        let max_msg_len = (overlay_width as usize).saturating_sub(30);
+        for commit in commits {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("    {} ", commit.short_hash),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::styled(
+                    format!(
+                        "{:<width$}",
+                        truncate_str(&commit.message, max_msg_len),
+                        width = max_msg_len
+                    ),
+                    Style::default().fg(Color::White),
+                ),
+                Span::styled(
+                    format!(" {}", format_time_ago(Some(commit.timestamp))),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        "  Press [q] to go back",
+        Style::default().fg(Color::DarkGray),
+    )]));
+
+    let overlay_height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let overlay_area = centered_rect(overlay_width, overlay_height, area);
+
+    // Clear the area behind the overlay
+    frame.render_widget(Clear, overlay_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan))
+        .title(Span::styled(
+            format!(" {} ", truncate_str(project_name, 40)),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+
+    let paragraph = Paragraph::new(lines).block(block);
+    frame.render_widget(paragraph, overlay_area);
+}
+
+/// Build the per-day bar chart (two columns per day), with the longest gap
+/// underlined and the first/last dates as labels
+fn chart_lines(summary: &ProjectSummary) -> Vec<Line<'static>> {
+    let max_count = summary
+        .daily
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let levels: Vec<usize> = summary
+        .daily
+        .iter()
+        .map(|(_, count)| {
+            if *count == 0 {
+                0
+            } else {
+                // Any commit shows at least a sliver
+                ((*count as usize * CHART_ROWS * 8) / max_count as usize).max(1)
+            }
+        })
+        .collect();
+
+    let mut lines = Vec::new();
+    for row in 0..CHART_ROWS {
+        let floor = (CHART_ROWS - 1 - row) * 8;
+        let bars: String = levels
+            .iter()
+            .map(|level| {
+                let fill = level.saturating_sub(floor).min(8);
+                let cell = if fill == 0 && row == CHART_ROWS - 1 {
+                    '·'
+                } else {
+                    BAR_LEVELS[fill]
+                };
+                format!("{cell}{cell}")
+            })
+            .collect();
+        lines.push(Line::from(vec![
+            Span::raw("    "),
+            Span::styled(bars, Style::default().fg(Color::Green)),
+        ]));
+    }
+
+    // Underline the days inside the longest gap when it falls in the window
+    if let Some((from, to)) = summary.longest_gap {
+        let marks: String = summary
+            .daily
+            .iter()
+            .map(|(date, _)| {
+                if *date > from && *date < to {
+                    "──"
+                } else {
+                    "  "
+                }
+            })
+            .collect();
+        if marks.contains('─') {
+            lines.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(marks, Style::default().fg(Color::Red)),
+            ]));
+        }
+    }
+
+    if let (Some((first, _)), Some((last, _))) = (summary.daily.first(), summary.daily.last()) {
+        let first = first.format("%b %d").to_string();
+        let last = last.format("%b %d").to_string();
+        let width = summary.daily.len() * 2;
+        lines.push(Line::from(vec![Span::styled(
+            format!(
+                "    {}{:>pad$}",
+                first,
+                last,
+                pad = width.saturating_sub(first.len())
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]));
+    }
+
+    lines
+}
+
+/// Build a one-row sparkline of the TODO count over the latest scanned commits,
+/// followed by the current count and its change over the shown range
+fn marker_trend_lines(summary: &ProjectSummary) -> Vec<Line<'static>> {
+    let trend = &summary.marker_trend[summary.marker_trend.len().saturating_sub(TREND_COMMITS)..];
+    let max_total = trend.iter().map(|m| m.total).max().unwrap_or(0).max(1);
+    let sparkline: String = trend
+        .iter()
+        .map(|m| {
+            if m.total == 0 {
+                '·'
+            } else {
+                BAR_LEVELS[((m.total as usize * 8) / max_total as usize).max(1)]
+            }
+        })
+        .collect();
+
+    let (Some(first), Some(last)) = (trend.first(), trend.last()) else {
+        return Vec::new();
+    };
+    // The first commit's own change counts too, it happened in the shown range
+    let change = last.total as i64 - first.total as i64 + first.change as i64;
+    let (change_text, change_color) = match change {
+        c if c < 0 => (format!("{} since", c), Color::Green),
+        c if c > 0 => (format!("+{} since", c), Color::Red),
+        _ => ("no change since".to_string(), Color::DarkGray),
+    };
+
+    vec![
+        Line::from(vec![
+            Span::raw("    "),
+            Span::styled(sparkline, Style::default().fg(Color::Yellow)),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                format!("    {} now", last.total),
+                Style::default()
+                    .fg(Color::White)
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!("  {} ", change_text),
+                Style::default().fg(change_color),
+            ),
+            Span::styled(
+                format!(
+                    "{} commit{} ago",
+                    trend.len(),
+                    if trend.len() == 1 { "" } else { "s" }
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]),
+    ]
+}
@@ -126,6 +126,71 @@ pub fn render_environment_background(frame: &mut Frame, env: &Environment, area:
             area,
         );
     }
+
+    // Render fireworks on top of the sky
+    render_fireworks(frame, env, area);
+}
+
+/// Render fireworks (release celebrations) in the sky
+fn render_fireworks(frame: &mut Frame, env: &Environment, area: Rect) {
+    const PALETTE: [Color; 6] = [
+        Color::LightRed,
+        Color::LightYellow,
+        Color::LightMagenta,
+        Color::LightCyan,
+        Color::LightGreen,
+        Color::LightBlue,
+    ];
+    // Spark directions, stretched horizontally since cells are taller than wide
+    const SPARKS: [(f32, f32); 8] = [
+        (2.0, 0.0),
+        (-2.0, 0.0),
+        (0.0, 1.0),
+        (0.0, -1.0),
+        (1.4, 0.7),
+        (-1.4, 0.7),
+        (1.4, -0.7),
+        (-1.4, -0.7),
+    ];
+
+    let mut draw = |x: f32, y: f32, ch: char, color: Color| {
+        let (x, y) = (x.round() as i32, y.round() as i32);
+        if x < 0 || y < 0 || x >= area.width as i32 || y >= area.height as i32 {
+            return;
+        }
+        let cell = Rect {
+            x: area.x + x as u16,
+            y: area.y + y as u16,
+            width: 1,
+            height: 1,
+        };
+        frame.render_widget(
+            Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
+            cell,
+        );
+    };
+
+    let ground_y = area.height.saturating_sub(2) as f32;
+    for firework in &env.fireworks {
+        let color = PALETTE[firework.color % PALETTE.len()];
+        if let Some((x, y)) = firework.rocket_position(ground_y) {
+            draw(x, y, '|', Color::Yellow);
+        }
+        if let Some((radius, progress)) = firework.burst() {
+            let ch = if progress < 0.6 { '*' } else { '.' };
+            for (dx, dy) in SPARKS {
+                draw(
+                    firework.x + dx * radius,
+                    firework.burst_y + dy * radius,
+                    ch,
+                    color,
+                );
+            }
+            if progress < 0.3 {
+                draw(firework.x, firework.burst_y, '@', Color::White);
+            }
+        }
+    }
 }
 
 /// Render the ground line at the bottom of the crab area
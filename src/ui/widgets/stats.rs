@@ -1,5 +1,6 @@
+use crate::crab::Mood;
 use crate::git::{format_time_ago, GitStats};
-use crate::state::{get_today_by_project, AppState};
+use crate::state::{get_today_by_project, AppState, Rules};
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -10,18 +11,21 @@ use ratatui::{
 
 use super::helpers::render_happiness_bar;
 
+/// Minimum gap between authoring and detection before the panel mentions it
+const DETECTION_LAG_MINUTES: i64 = 5;
+
 /// Render the stats panel
 pub fn render_stats(
     frame: &mut Frame,
     stats: &GitStats,
     app_state: &AppState,
+    rules: &Rules,
+    mood: Mood,
     happiness: u8,
     area: Rect,
 ) {
-    let mood = crate::crab::Mood::from_happiness(happiness);
-
     // Get commits today from tracked history
-    let commits_today = get_today_by_project(&app_state.commit_history)
+    let commits_today = get_today_by_project(&app_state.commit_history, rules)
         .iter()
         .map(|(_, _, count)| count)
         .sum::<u32>();
@@ -42,10 +46,10 @@ pub fn render_stats(
     if stats.in_git_repo {
         // Show repo info - single name or count for multiple
         if stats.repo_count == 1 {
-            if let Some(name) = stats.repo_names.first() {
+            if let Some(repo) = stats.repos.first() {
                 lines.push(Line::from(vec![
                     Span::styled("  Repo: ", Style::default().fg(Color::DarkGray)),
-                    Span::styled(name.clone(), Style::default().fg(Color::Cyan)),
+                    Span::styled(repo.name.clone(), Style::default().fg(Color::Cyan)),
                 ]));
             }
         } else {
@@ -98,13 +102,73 @@ pub fn render_stats(
             ]));
         }
 
-        lines.push(Line::from(vec![
+        let mut last_commit_spans = vec![
             Span::styled("  Last commit: ", Style::default().fg(Color::DarkGray)),
             Span::styled(
                 format_time_ago(app_state.last_commit_time),
                 Style::default().fg(Color::White),
             ),
-        ]));
+        ];
+
+        // Point out commits that were only noticed well after they were made
+        let detected_at = app_state
+            .commit_history
+            .iter()
+            .max_by_key(|commit| commit.timestamp)
+            .and_then(|commit| Some((commit.timestamp, commit.detected_at?)));
+        if let Some((authored, detected)) = detected_at {
+            if detected.signed_duration_since(authored).num_minutes() >= DETECTION_LAG_MINUTES {
+                last_commit_spans.push(Span::styled(
+                    format!(" (seen {})", format_time_ago(Some(detected))),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+        }
+
+        lines.push(Line::from(last_commit_spans));
+
+        let unpushed = stats.unpushed_total();
+        if unpushed > 0 {
+            let mut unpushed_spans = vec![
+                Span::styled("  Unpushed: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!(
+                        "{} commit{}",
+                        unpushed,
+                        if unpushed == 1 { "" } else { "s" }
+                    ),
+                    Style::default().fg(Color::Yellow),
+                ),
+            ];
+            if let Some(oldest) = stats.oldest_unpushed().and_then(|r| r.oldest_unpushed) {
+                unpushed_spans.push(Span::styled(
+                    format!(" (oldest {})", format_time_ago(Some(oldest))),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(unpushed_spans));
+        }
+
+        let status = stats.working_tree_total();
+        if status.is_dirty() {
+            let mut status_spans = vec![
+                Span::styled("  Uncommitted: ", Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    format!(
+                        "{} modified, {} staged, {} untracked",
+                        status.modified, status.staged, status.untracked
+                    ),
+                    Style::default().fg(Color::LightRed),
+                ),
+            ];
+            if status.oldest_change.is_some() {
+                status_spans.push(Span::styled(
+                    format!(" (oldest {})", format_time_ago(status.oldest_change)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            lines.push(Line::from(status_spans));
+        }
     } else {
         lines.push(Line::from(vec![Span::styled(
             "  No git repositories found",